@@ -0,0 +1,40 @@
+/*
+ * Recognize a screenshot with the C API.
+ *
+ * capi$ cargo build --release
+ * capi$ cc examples/recognize.c -Iinclude -L../target/release -lwordfeud_ocr_capi -o recognize
+ * capi$ LD_LIBRARY_PATH=../target/release ./recognize ../lib/tests/screenshot_english.png
+ */
+#include <stdio.h>
+#include "wordfeud_ocr.h"
+
+int main(int argc, char **argv) {
+    if (argc != 2) {
+        fprintf(stderr, "usage: %s SCREENSHOT\n", argv[0]);
+        return 2;
+    }
+    FILE *file = fopen(argv[1], "rb");
+    if (file == NULL) {
+        perror(argv[1]);
+        return 3;
+    }
+    fseek(file, 0, SEEK_END);
+    long len = ftell(file);
+    fseek(file, 0, SEEK_SET);
+    uint8_t *data = malloc(len);
+    size_t read = fread(data, 1, len, file);
+    fclose(file);
+
+    WfBoard *board = wordfeud_ocr_board_new();
+    WfResult *result = wordfeud_ocr_recognize(board, data, read);
+    int status = wordfeud_ocr_result_status(result);
+    if (status == WF_STATUS_OK) {
+        puts(wordfeud_ocr_result_json(result));
+    } else {
+        fprintf(stderr, "%s: %s\n", argv[1], wordfeud_ocr_result_error(result));
+    }
+    wordfeud_ocr_result_free(result);
+    wordfeud_ocr_board_free(board);
+    free(data);
+    return status;
+}
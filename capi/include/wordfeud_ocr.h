@@ -0,0 +1,122 @@
+/* Generated with cbindgen from capi/src/lib.rs. Do not edit. */
+
+#ifndef WORDFEUD_OCR_H
+#define WORDFEUD_OCR_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The status of a recognition
+typedef enum WfStatus {
+  // The screenshot is recognized
+  WF_STATUS_OK = 0,
+  // A NULL pointer or an unknown pixel format was passed
+  WF_STATUS_INVALID_ARGUMENT = 1,
+  // The screenshot can not be decoded
+  WF_STATUS_IMAGE_ERROR = 2,
+  // The board layout is not recognized
+  WF_STATUS_LAYOUT_FAILED = 3,
+  // The detected board is not square
+  WF_STATUS_BOARD_NOT_SQUARE = 4,
+  // The pixel buffer does not match its dimensions and pixel format
+  WF_STATUS_INVALID_BUFFER = 5,
+  // The recognition panicked
+  WF_STATUS_PANIC = 6,
+  // An internal error, e.g. the results can not be serialized, or an error that this version of the
+  // C API does not know
+  WF_STATUS_INTERNAL = 7,
+} WfStatus;
+
+// The pixel format of a screenshot buffer
+typedef enum WfPixelFormat {
+  // 8 bit grayscale
+  WF_PIXEL_FORMAT_GRAY8 = 0,
+  // 8 bit red, green, blue, alpha
+  WF_PIXEL_FORMAT_RGBA8 = 1,
+  // 8 bit blue, green, red, alpha, as delivered by Android screen capture
+  WF_PIXEL_FORMAT_BGRA8 = 2,
+} WfPixelFormat;
+
+// A Wordfeud board recognizer
+typedef struct WfBoard WfBoard;
+
+// The result of a recognition: the JSON serialized results, or an error message
+typedef struct WfResult WfResult;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Return the version of the library, e.g. `"0.1.0"`. The string is static.
+const char *wordfeud_ocr_version(void);
+
+// Create a new board. It must be freed with `wordfeud_ocr_board_free`.
+struct WfBoard *wordfeud_ocr_board_new(void);
+
+// Free a board.
+//
+// # Safety
+// `board` must be NULL, or a board from `wordfeud_ocr_board_new` that is not freed yet.
+void wordfeud_ocr_board_free(struct WfBoard *board);
+
+// Recognize a screenshot file (PNG, JPEG or WebP) that is loaded in memory.
+//
+// Returns a result that must be freed with `wordfeud_ocr_result_free`.
+//
+// # Safety
+// `board` must be a valid board, and `data` must point to `len` readable bytes.
+struct WfResult *wordfeud_ocr_recognize(const struct WfBoard *board,
+                                        const uint8_t *data,
+                                        size_t len);
+
+// Recognize a screenshot in a pixel buffer with `height` rows of `width` pixels, and `stride` bytes per row.
+// `format` is a `WfPixelFormat` value, an unknown value gives status `WF_STATUS_INVALID_ARGUMENT`.
+//
+// Returns a result that must be freed with `wordfeud_ocr_result_free`.
+//
+// # Safety
+// `board` must be a valid board, and `pixels` must point to `(height - 1) * stride + width * bytes per pixel`
+// readable bytes: the last row needs no padding.
+struct WfResult *wordfeud_ocr_recognize_pixels(const struct WfBoard *board,
+                                               const uint8_t *pixels,
+                                               uint32_t width,
+                                               uint32_t height,
+                                               size_t stride,
+                                               uint32_t format);
+
+// Return the status of a result. A NULL result has status `WF_STATUS_INVALID_ARGUMENT`.
+//
+// # Safety
+// `result` must be NULL, or a result that is not freed yet.
+enum WfStatus wordfeud_ocr_result_status(const struct WfResult *result);
+
+// Return the recognized screenshot as a JSON object, or NULL if the recognition failed.
+//
+// The string is owned by the result, and is valid until the result is freed.
+//
+// # Safety
+// `result` must be NULL, or a result that is not freed yet.
+const char *wordfeud_ocr_result_json(const struct WfResult *result);
+
+// Return the error message, or NULL if the screenshot is recognized.
+//
+// The string is owned by the result, and is valid until the result is freed.
+//
+// # Safety
+// `result` must be NULL, or a result that is not freed yet.
+const char *wordfeud_ocr_result_error(const struct WfResult *result);
+
+// Free a result.
+//
+// # Safety
+// `result` must be NULL, or a result that is not freed yet.
+void wordfeud_ocr_result_free(struct WfResult *result);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* WORDFEUD_OCR_H */
@@ -0,0 +1,278 @@
+//! C API for the wordfeud-ocr library.
+//!
+//! The API is declared in `include/wordfeud_ocr.h`, which is generated with [cbindgen](https://github.com/eqrion/cbindgen).
+//! A board is created once with `wordfeud_ocr_board_new`, and can be used from several threads.
+//! Each recognition returns a result that holds either the JSON serialized
+//! [OcrResults](wordfeud_ocr::OcrResults), or an error message. The result owns its strings, and must be freed
+//! with `wordfeud_ocr_result_free`.
+//!
+//! ```c
+//! WfBoard *board = wordfeud_ocr_board_new();
+//! WfResult *result = wordfeud_ocr_recognize(board, data, len);
+//! if (wordfeud_ocr_result_status(result) == WF_STATUS_OK) {
+//!     puts(wordfeud_ocr_result_json(result));
+//! } else {
+//!     fprintf(stderr, "%s\n", wordfeud_ocr_result_error(result));
+//! }
+//! wordfeud_ocr_result_free(result);
+//! wordfeud_ocr_board_free(board);
+//! ```
+use std::convert::TryFrom;
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+use wordfeud_ocr::{Board, Error, OcrResults, PixelFormat};
+
+/// A Wordfeud board recognizer
+pub struct WfBoard(Board);
+
+/// The result of a recognition: the JSON serialized results, or an error message
+pub struct WfResult {
+    status: WfStatus,
+    json: Option<CString>,
+    error: Option<CString>,
+}
+
+/// The status of a recognition
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WfStatus {
+    /// The screenshot is recognized
+    Ok = 0,
+    /// A NULL pointer or an unknown pixel format was passed
+    InvalidArgument = 1,
+    /// The screenshot can not be decoded
+    ImageError = 2,
+    /// The board layout is not recognized
+    LayoutFailed = 3,
+    /// The detected board is not square
+    BoardNotSquare = 4,
+    /// The pixel buffer does not match its dimensions and pixel format
+    InvalidBuffer = 5,
+    /// The recognition panicked
+    Panic = 6,
+    /// An internal error, e.g. the results can not be serialized, or an error that this version of the
+    /// C API does not know
+    Internal = 7,
+}
+
+/// The pixel format of a screenshot buffer
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WfPixelFormat {
+    /// 8 bit grayscale
+    Gray8 = 0,
+    /// 8 bit red, green, blue, alpha
+    Rgba8 = 1,
+    /// 8 bit blue, green, red, alpha, as delivered by Android screen capture
+    Bgra8 = 2,
+}
+
+/// Convert a `WfPixelFormat` value from C. An unknown value is returned as the error.
+impl TryFrom<u32> for WfPixelFormat {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, u32> {
+        match value {
+            0 => Ok(WfPixelFormat::Gray8),
+            1 => Ok(WfPixelFormat::Rgba8),
+            2 => Ok(WfPixelFormat::Bgra8),
+            _ => Err(value),
+        }
+    }
+}
+
+impl From<WfPixelFormat> for PixelFormat {
+    fn from(format: WfPixelFormat) -> Self {
+        match format {
+            WfPixelFormat::Gray8 => PixelFormat::Gray8,
+            WfPixelFormat::Rgba8 => PixelFormat::Rgba8,
+            WfPixelFormat::Bgra8 => PixelFormat::Bgra8,
+        }
+    }
+}
+
+/// Convert to a C string. Interior NUL bytes are removed.
+fn c_string(s: &str) -> CString {
+    CString::new(s.replace('\0', "")).unwrap_or_default()
+}
+
+impl WfResult {
+    fn error(status: WfStatus, message: &str) -> WfResult {
+        WfResult {
+            status,
+            json: None,
+            error: Some(c_string(message)),
+        }
+    }
+
+    fn new(result: Result<OcrResults, Error>) -> WfResult {
+        match result {
+            Ok(res) => match serde_json::to_string(&res) {
+                Ok(json) => WfResult {
+                    status: WfStatus::Ok,
+                    json: Some(c_string(&json)),
+                    error: None,
+                },
+                Err(err) => WfResult::error(WfStatus::Internal, &err.to_string()),
+            },
+            Err(err) => {
+                let status = match err {
+                    Error::BoardNotSquare(_) => WfStatus::BoardNotSquare,
+                    Error::LayoutFailed(_) => WfStatus::LayoutFailed,
+                    Error::InvalidBuffer(_) => WfStatus::InvalidBuffer,
+                    Error::ImageError(_) => WfStatus::ImageError,
+                    _ => WfStatus::Internal,
+                };
+                WfResult::error(status, &err.message())
+            }
+        }
+    }
+
+    /// Run `f`, and return its result as a heap allocated `WfResult`. A panic is returned as an error.
+    fn boxed<F>(f: F) -> *mut WfResult
+    where
+        F: FnOnce() -> WfResult,
+    {
+        let result = catch_unwind(AssertUnwindSafe(f))
+            .unwrap_or_else(|_| WfResult::error(WfStatus::Panic, "Recognition panicked"));
+        Box::into_raw(Box::new(result))
+    }
+}
+
+/// Return the version of the library, e.g. `"0.1.0"`. The string is static.
+#[no_mangle]
+pub extern "C" fn wordfeud_ocr_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr() as *const c_char
+}
+
+/// Create a new board. It must be freed with `wordfeud_ocr_board_free`.
+#[no_mangle]
+pub extern "C" fn wordfeud_ocr_board_new() -> *mut WfBoard {
+    Box::into_raw(Box::new(WfBoard(Board::new())))
+}
+
+/// Free a board.
+///
+/// # Safety
+/// `board` must be NULL, or a board from `wordfeud_ocr_board_new` that is not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn wordfeud_ocr_board_free(board: *mut WfBoard) {
+    if !board.is_null() {
+        drop(Box::from_raw(board));
+    }
+}
+
+/// Recognize a screenshot file (PNG, JPEG or WebP) that is loaded in memory.
+///
+/// Returns a result that must be freed with `wordfeud_ocr_result_free`.
+///
+/// # Safety
+/// `board` must be a valid board, and `data` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn wordfeud_ocr_recognize(
+    board: *const WfBoard,
+    data: *const u8,
+    len: usize,
+) -> *mut WfResult {
+    WfResult::boxed(|| {
+        if board.is_null() || data.is_null() {
+            return WfResult::error(WfStatus::InvalidArgument, "NULL board or data");
+        }
+        let data = slice::from_raw_parts(data, len);
+        WfResult::new((*board).0.recognize_screenshot_from_memory(data))
+    })
+}
+
+/// Recognize a screenshot in a pixel buffer with `height` rows of `width` pixels, and `stride` bytes per row.
+/// `format` is a `WfPixelFormat` value, an unknown value gives status `WF_STATUS_INVALID_ARGUMENT`.
+///
+/// Returns a result that must be freed with `wordfeud_ocr_result_free`.
+///
+/// # Safety
+/// `board` must be a valid board, and `pixels` must point to `(height - 1) * stride + width * bytes per pixel`
+/// readable bytes: the last row needs no padding.
+#[no_mangle]
+pub unsafe extern "C" fn wordfeud_ocr_recognize_pixels(
+    board: *const WfBoard,
+    pixels: *const u8,
+    width: u32,
+    height: u32,
+    stride: usize,
+    format: u32,
+) -> *mut WfResult {
+    WfResult::boxed(|| {
+        if board.is_null() || pixels.is_null() {
+            return WfResult::error(WfStatus::InvalidArgument, "NULL board or pixels");
+        }
+        let format = match WfPixelFormat::try_from(format) {
+            Ok(format) => PixelFormat::from(format),
+            Err(value) => {
+                let message = format!("Unknown pixel format {}", value);
+                return WfResult::error(WfStatus::InvalidArgument, &message);
+            }
+        };
+        let len = match format.buffer_len(width, height, stride) {
+            Some(len) => len,
+            None => return WfResult::error(WfStatus::InvalidBuffer, "Buffer size overflows"),
+        };
+        let pixels = slice::from_raw_parts(pixels, len);
+        WfResult::new(
+            (*board)
+                .0
+                .recognize_buffer(pixels, width, height, stride, format),
+        )
+    })
+}
+
+/// Return the status of a result. A NULL result has status `WF_STATUS_INVALID_ARGUMENT`.
+///
+/// # Safety
+/// `result` must be NULL, or a result that is not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn wordfeud_ocr_result_status(result: *const WfResult) -> WfStatus {
+    result
+        .as_ref()
+        .map_or(WfStatus::InvalidArgument, |result| result.status)
+}
+
+/// Return the recognized screenshot as a JSON object, or NULL if the recognition failed.
+///
+/// The string is owned by the result, and is valid until the result is freed.
+///
+/// # Safety
+/// `result` must be NULL, or a result that is not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn wordfeud_ocr_result_json(result: *const WfResult) -> *const c_char {
+    result
+        .as_ref()
+        .and_then(|result| result.json.as_ref())
+        .map_or(ptr::null(), |json| json.as_ptr())
+}
+
+/// Return the error message, or NULL if the screenshot is recognized.
+///
+/// The string is owned by the result, and is valid until the result is freed.
+///
+/// # Safety
+/// `result` must be NULL, or a result that is not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn wordfeud_ocr_result_error(result: *const WfResult) -> *const c_char {
+    result
+        .as_ref()
+        .and_then(|result| result.error.as_ref())
+        .map_or(ptr::null(), |error| error.as_ptr())
+}
+
+/// Free a result.
+///
+/// # Safety
+/// `result` must be NULL, or a result that is not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn wordfeud_ocr_result_free(result: *mut WfResult) {
+    if !result.is_null() {
+        drop(Box::from_raw(result));
+    }
+}
@@ -0,0 +1,120 @@
+use std::ffi::CStr;
+use std::path::Path;
+use wordfeud_ocr_capi::*;
+
+unsafe fn c_str<'a>(s: *const std::os::raw::c_char) -> Option<&'a str> {
+    if s.is_null() {
+        None
+    } else {
+        Some(CStr::from_ptr(s).to_str().unwrap())
+    }
+}
+
+#[test]
+fn test_recognize() {
+    let screenshot = std::fs::read("../lib/tests/screenshot_english.png").unwrap();
+    unsafe {
+        let board = wordfeud_ocr_board_new();
+        let result = wordfeud_ocr_recognize(board, screenshot.as_ptr(), screenshot.len());
+        assert_eq!(wordfeud_ocr_result_status(result), WfStatus::Ok);
+        assert_eq!(c_str(wordfeud_ocr_result_error(result)), None);
+        let json = c_str(wordfeud_ocr_result_json(result)).unwrap().to_string();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["tiles_ocr"][2][12], "z");
+        wordfeud_ocr_result_free(result);
+
+        let gray = image::load_from_memory(&screenshot).unwrap().into_luma8();
+        let (width, height) = gray.dimensions();
+        let result = wordfeud_ocr_recognize_pixels(
+            board,
+            gray.as_ptr(),
+            width,
+            height,
+            width as usize,
+            WfPixelFormat::Gray8 as u32,
+        );
+        assert_eq!(wordfeud_ocr_result_status(result), WfStatus::Ok);
+        assert_eq!(c_str(wordfeud_ocr_result_json(result)), Some(json.as_str()));
+        wordfeud_ocr_result_free(result);
+        wordfeud_ocr_board_free(board);
+    }
+}
+
+#[test]
+fn test_errors() {
+    let logo = std::fs::read("../images/logo.png").unwrap();
+    unsafe {
+        let board = wordfeud_ocr_board_new();
+        let result = wordfeud_ocr_recognize(board, logo.as_ptr(), logo.len());
+        assert_eq!(wordfeud_ocr_result_status(result), WfStatus::LayoutFailed);
+        assert_eq!(c_str(wordfeud_ocr_result_json(result)), None);
+        assert_eq!(
+            c_str(wordfeud_ocr_result_error(result)),
+            Some("Failed to create layout")
+        );
+        wordfeud_ocr_result_free(result);
+
+        let result = wordfeud_ocr_recognize(board, b"garbage".as_ptr(), 7);
+        assert_eq!(wordfeud_ocr_result_status(result), WfStatus::ImageError);
+        wordfeud_ocr_result_free(result);
+
+        let result = wordfeud_ocr_recognize_pixels(
+            board,
+            logo.as_ptr(),
+            100,
+            100,
+            10,
+            WfPixelFormat::Rgba8 as u32,
+        );
+        assert_eq!(wordfeud_ocr_result_status(result), WfStatus::InvalidBuffer);
+        wordfeud_ocr_result_free(result);
+
+        let result = wordfeud_ocr_recognize_pixels(board, logo.as_ptr(), 10, 10, 10, 7);
+        assert_eq!(
+            wordfeud_ocr_result_status(result),
+            WfStatus::InvalidArgument
+        );
+        assert_eq!(
+            c_str(wordfeud_ocr_result_error(result)),
+            Some("Unknown pixel format 7")
+        );
+        wordfeud_ocr_result_free(result);
+
+        let result = wordfeud_ocr_recognize(std::ptr::null(), logo.as_ptr(), logo.len());
+        assert_eq!(
+            wordfeud_ocr_result_status(result),
+            WfStatus::InvalidArgument
+        );
+        wordfeud_ocr_result_free(result);
+        assert_eq!(
+            wordfeud_ocr_result_status(std::ptr::null()),
+            WfStatus::InvalidArgument
+        );
+        wordfeud_ocr_board_free(board);
+        assert_eq!(
+            c_str(wordfeud_ocr_version()),
+            Some(env!("CARGO_PKG_VERSION"))
+        );
+    }
+}
+
+/// The header must be regenerated when the API changes: `UPDATE_HEADER=1 cargo test -p wordfeud-ocr-capi`.
+#[test]
+fn test_header_is_up_to_date() {
+    let crate_dir = env!("CARGO_MANIFEST_DIR");
+    let config = cbindgen::Config::from_file(Path::new(crate_dir).join("cbindgen.toml")).unwrap();
+    let mut header = Vec::new();
+    cbindgen::generate_with_config(crate_dir, config)
+        .unwrap()
+        .write(&mut header);
+    let path = Path::new(crate_dir).join("include/wordfeud_ocr.h");
+    if std::env::var_os("UPDATE_HEADER").is_some() {
+        std::fs::write(&path, &header).unwrap();
+    }
+    let current = std::fs::read(&path).unwrap_or_default();
+    assert!(
+        current == header,
+        "{} is out of date, run with UPDATE_HEADER=1",
+        path.display()
+    );
+}
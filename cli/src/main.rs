@@ -0,0 +1,485 @@
+//! Command line interface for the wordfeud-ocr library.
+//!
+//! Exit codes: 0 on success, 1 on other failures, 2 on usage errors, 3 if a screenshot can not be read
+//! or an output file can not be written, 4 if the board layout of a screenshot is not recognized, and 5 if
+//! a screenshot is corrupt or in an unsupported format.
+//! If several screenshots fail, the exit code is that of the first failure.
+mod output;
+
+use clap::{ArgEnum, Args, Parser, Subcommand};
+use image::{io::Reader as ImageReader, DynamicImage, GrayImage, ImageFormat};
+use imageproc::drawing::draw_antialiased_line_segment_mut;
+use imageproc::pixelops::interpolate;
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use wordfeud_ocr::{
+    collage, into_gray, save_templates, Board, Dictionary, Error, Game, GcgError, Language, Layout,
+    Solver, Watcher,
+};
+
+#[derive(Parser)]
+#[clap(
+    name = "wordfeud-ocr",
+    version,
+    about = "Recognize screenshots from the Wordfeud game",
+    after_help = "EXIT CODES:\n    0    Success\n    1    Other failures\n    2    Usage errors\n    \
+                  3    A screenshot can not be read, or an output file can not be written\n    \
+                  4    The board layout of a screenshot is not recognized\n    \
+                  5    A screenshot is corrupt or in an unsupported format\n\n\
+                  If several screenshots fail, the exit code is that of the first failure."
+)]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Recognize the tiles on the board and the rack, and the bonus squares
+    Recognize {
+        #[clap(flatten)]
+        input: Input,
+        /// Output format
+        #[clap(short, long, arg_enum, default_value = "text")]
+        format: Format,
+        /// Print a summary with the success rate, failure reasons and timing on stderr
+        #[clap(short, long)]
+        report: bool,
+        /// Correct OCR errors with the words in this word list, with one word per line.
+        /// The corrections are printed on stderr
+        #[clap(short, long)]
+        dictionary: Option<PathBuf>,
+        /// The language of the game, for the tiles of the corrections: en, nl, sv or es
+        #[clap(short, long, default_value = "en")]
+        language: Language,
+    },
+    /// Find the best moves on the board with the tiles on the rack
+    Solve {
+        #[clap(flatten)]
+        input: Input,
+        /// The word list, with one word per line
+        #[clap(short, long)]
+        dictionary: PathBuf,
+        /// The language of the game: en, nl, sv or es
+        #[clap(short, long, default_value = "en")]
+        language: Language,
+        /// The number of moves to show for each screenshot
+        #[clap(short = 'n', long, default_value = "10")]
+        count: usize,
+    },
+    /// Write a game record in the GCG format, from consecutive screenshots of one game
+    Gcg {
+        #[clap(flatten)]
+        input: Input,
+        /// The language of the game: en, nl, sv or es
+        #[clap(short, long, default_value = "en")]
+        language: Language,
+    },
+    /// Show the board and rack layout
+    Layout {
+        #[clap(flatten)]
+        input: Input,
+        /// Output format
+        #[clap(short, long, arg_enum, default_value = "text")]
+        format: Format,
+    },
+    /// Draw the detected rows and columns on the screenshots
+    Overlay {
+        #[clap(flatten)]
+        input: Input,
+        /// Directory for the `<name>_overlay.png` images
+        #[clap(short, long, default_value = ".")]
+        output_dir: PathBuf,
+    },
+    /// Create a collage of the tiles on the board and in the rack
+    Collage {
+        #[clap(flatten)]
+        input: Input,
+        /// Directory for the `<name>_collage.png` images
+        #[clap(short, long, default_value = ".")]
+        output_dir: PathBuf,
+    },
+    /// Save the recognized tiles as templates, for letters that have no template yet
+    HarvestTemplates {
+        #[clap(flatten)]
+        input: Input,
+        /// Directory for the templates
+        #[clap(short, long)]
+        dir: PathBuf,
+    },
+    /// Watch a directory, and recognize each new PNG screenshot into a JSON sidecar next to it
+    Watch {
+        /// The directory with the screenshots
+        dir: PathBuf,
+        /// Poll interval in milliseconds
+        #[clap(short, long, default_value = "1000")]
+        interval: u64,
+    },
+}
+
+#[derive(Args)]
+struct Input {
+    /// Screenshot files or glob patterns, like `screenshots/*.png`
+    #[clap(required = true)]
+    screenshots: Vec<String>,
+}
+
+impl Input {
+    /// Expand the glob patterns. Patterns without a match are kept, so they are reported as missing files.
+    fn paths(&self) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        for arg in &self.screenshots {
+            let matches: Vec<PathBuf> = glob::glob(arg)
+                .map(|entries| entries.filter_map(Result::ok).collect())
+                .unwrap_or_default();
+            if matches.is_empty() {
+                paths.push(PathBuf::from(arg));
+            } else {
+                paths.extend(matches);
+            }
+        }
+        paths
+    }
+}
+
+/// Output format
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+/// The kind of failure, which determines the exit code
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FailureKind {
+    Io,
+    Layout,
+    Decode,
+    Other,
+}
+
+impl FailureKind {
+    fn exit_code(self) -> i32 {
+        match self {
+            FailureKind::Other => 1,
+            FailureKind::Io => 3,
+            FailureKind::Layout => 4,
+            FailureKind::Decode => 5,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FailureKind::Io => "io",
+            FailureKind::Layout => "layout",
+            FailureKind::Decode => "decode",
+            FailureKind::Other => "other",
+        }
+    }
+}
+
+/// A failure to process a screenshot
+#[derive(Debug)]
+pub struct Failure {
+    pub kind: FailureKind,
+    pub message: String,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl From<Error> for Failure {
+    fn from(err: Error) -> Self {
+        let kind = match err {
+            Error::BoardNotSquare(_) | Error::LayoutFailed(_) => FailureKind::Layout,
+            Error::ImageError(image::ImageError::IoError(_)) => FailureKind::Io,
+            Error::ImageError(_) => FailureKind::Decode,
+            _ => FailureKind::Other,
+        };
+        Failure {
+            kind,
+            message: err.message(),
+        }
+    }
+}
+
+impl From<image::ImageError> for Failure {
+    fn from(err: image::ImageError) -> Self {
+        Failure::from(Error::from(err))
+    }
+}
+
+impl From<GcgError> for Failure {
+    fn from(err: GcgError) -> Self {
+        let mut message = err.to_string();
+        if let GcgError::Inconsistent {
+            inconsistencies, ..
+        } = &err
+        {
+            for inconsistency in inconsistencies {
+                message = format!("{}\n  {}", message, inconsistency);
+            }
+        }
+        Failure {
+            kind: FailureKind::Other,
+            message,
+        }
+    }
+}
+
+impl From<std::io::Error> for Failure {
+    fn from(err: std::io::Error) -> Self {
+        Failure {
+            kind: FailureKind::Io,
+            message: err.to_string(),
+        }
+    }
+}
+
+/// The result of processing a single screenshot
+pub type Outcome<T> = (PathBuf, Result<T, Failure>);
+
+/// Report a failure on stderr.
+fn outcome<T>(path: PathBuf, res: Result<T, Failure>) -> Outcome<T> {
+    if let Err(failure) = &res {
+        eprintln!("{}: {}", path.display(), failure);
+    }
+    (path, res)
+}
+
+/// Process each of the screenshots with `f`. Failures are reported on stderr.
+fn process<T, F>(paths: Vec<PathBuf>, mut f: F) -> Vec<Outcome<T>>
+where
+    F: FnMut(&Path) -> Result<T, Failure>,
+{
+    paths
+        .into_iter()
+        .map(|path| {
+            let res = f(&path);
+            outcome(path, res)
+        })
+        .collect()
+}
+
+/// Return the exit code of the first failure, or 0.
+fn exit_code<T>(outcomes: &[Outcome<T>]) -> i32 {
+    outcomes
+        .iter()
+        .find_map(|(_, res)| res.as_ref().err())
+        .map_or(0, |failure| failure.kind.exit_code())
+}
+
+/// Open a screenshot, and return the decoded image with its format.
+fn open(path: &Path) -> Result<(DynamicImage, Option<ImageFormat>), Failure> {
+    let reader = ImageReader::open(path)?.with_guessed_format()?;
+    let format = reader.format();
+    Ok((reader.decode()?, format))
+}
+
+/// Open a screenshot as a gray image, like the library does for recognition.
+fn open_gray(path: &Path) -> Result<GrayImage, Failure> {
+    let (img, format) = open(path)?;
+    Ok(into_gray(img, format))
+}
+
+/// Return the path of the output image for `screenshot` in `dir`, e.g. `dir/screenshot_overlay.png`.
+fn output_path(dir: &Path, screenshot: &Path, suffix: &str) -> PathBuf {
+    let stem = screenshot.file_stem().unwrap_or_default().to_string_lossy();
+    dir.join(format!("{}_{}.png", stem, suffix))
+}
+
+/// Draw the rows and columns of the board and the rack in `img`.
+fn draw_layout(img: &mut DynamicImage, layout: &Layout) {
+    let red = image::Rgba([255, 0, 0, 255]);
+    let blue = image::Rgba([0, 0, 255, 255]);
+    let mut line = |start: (i32, i32), end: (i32, i32), color| {
+        draw_antialiased_line_segment_mut(img, start, end, color, interpolate)
+    };
+    let (x, w) = (layout.board_area.x as i32, layout.board_area.width as i32);
+    for &(y0, y1) in layout.rows.iter() {
+        line((x, y0 as i32), (x + w, y0 as i32), red);
+        line((x, y1 as i32), (x + w, y1 as i32), blue);
+    }
+    for (area, cols) in &[
+        (layout.board_area, &layout.cols),
+        (layout.rack_area, &layout.rack_cols),
+    ] {
+        let (y0, y1) = (area.y as i32, (area.y + area.height) as i32);
+        for &(x0, x1) in cols.iter() {
+            line((x0 as i32, y0), (x0 as i32, y1), red);
+            line((x1 as i32, y0), (x1 as i32, y1), blue);
+        }
+    }
+}
+
+fn overlay(path: &Path, output_dir: &Path) -> Result<PathBuf, Failure> {
+    let (mut img, format) = open(path)?;
+    let layout = Layout::new(&into_gray(img.clone(), format)).segment()?;
+    draw_layout(&mut img, &layout);
+    let output = output_path(output_dir, path, "overlay");
+    img.save(&output)?;
+    Ok(output)
+}
+
+fn tile_collage(path: &Path, output_dir: &Path) -> Result<PathBuf, Failure> {
+    let gray = open_gray(path)?;
+    let layout = Layout::new(&gray).segment()?;
+    let cells = Layout::get_cells(&layout.rows, &layout.cols);
+    let index = layout.get_tile_index(&cells);
+    let mut tiles: Vec<_> = index.iter().map(|&i| cells[i]).collect();
+    tiles.extend(Layout::get_cells(&layout.rack_rows, &layout.rack_cols));
+    let output = output_path(output_dir, path, "collage");
+    collage(&gray, &tiles, None).save(&output)?;
+    Ok(output)
+}
+
+fn harvest(board: &Board, path: &Path, dir: &Path) -> Result<(), Failure> {
+    let gray = open_gray(path)?;
+    let layout = Layout::new(&gray).segment()?;
+    let (tiles, _) = board.recognize_tiles(&gray, &layout);
+    let cells = Layout::get_cells(&layout.rows, &layout.cols);
+    save_templates(dir, &gray, &cells, &tiles);
+    Ok(())
+}
+
+/// Print the written output files, and return the exit code.
+fn report_written(outcomes: &[Outcome<PathBuf>]) -> i32 {
+    for (_, res) in outcomes {
+        if let Ok(output) = res {
+            println!("{}", output.display());
+        }
+    }
+    exit_code(outcomes)
+}
+
+fn run(cli: Cli) -> Result<i32, Failure> {
+    let board = Board::new();
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    let code = match cli.command {
+        Command::Recognize {
+            input,
+            format,
+            report,
+            dictionary,
+            language,
+        } => {
+            let dictionary = dictionary.map(Dictionary::from_file).transpose()?;
+            let batch = board.recognize_batch(&input.paths());
+            let mut outcomes: Vec<_> = batch
+                .items
+                .into_iter()
+                .map(|item| outcome(item.path, item.result.map_err(Failure::from)))
+                .collect();
+            if let Some(dictionary) = &dictionary {
+                for (path, res) in outcomes.iter_mut() {
+                    if let Ok(res) = res {
+                        for correction in res.correct_words(dictionary, language).corrections {
+                            eprintln!("{}: {}", path.display(), correction);
+                        }
+                    }
+                }
+            }
+            output::write_results(&mut out, format, &outcomes)?;
+            if report {
+                eprintln!("{}", batch.report);
+            }
+            exit_code(&outcomes)
+        }
+        Command::Solve {
+            input,
+            dictionary,
+            language,
+            count,
+        } => {
+            let solver = Solver::new(&Dictionary::from_file(dictionary)?, language);
+            let outcomes = process(input.paths(), |path| {
+                let res = board.recognize_path(path)?;
+                Ok(solver.moves(&res))
+            });
+            for (path, res) in &outcomes {
+                if let Ok(moves) = res {
+                    writeln!(out, "Screenshot: {}", path.display())?;
+                    for m in moves.iter().take(count) {
+                        writeln!(out, "{}", m)?;
+                    }
+                }
+            }
+            exit_code(&outcomes)
+        }
+        Command::Gcg { input, language } => {
+            let outcomes = process(input.paths(), |path| Ok(board.recognize_path(path)?));
+            match exit_code(&outcomes) {
+                0 => {
+                    let screenshots: Vec<_> = outcomes
+                        .into_iter()
+                        .filter_map(|(_, res)| res.ok())
+                        .collect();
+                    write!(
+                        out,
+                        "{}",
+                        Game::from_screenshots(&screenshots, language)?.to_gcg()?
+                    )?;
+                    0
+                }
+                code => code,
+            }
+        }
+        Command::Layout { input, format } => {
+            let outcomes = process(input.paths(), |path| {
+                Ok(Layout::new(&open_gray(path)?).segment()?)
+            });
+            output::write_layouts(&mut out, format, &outcomes)?;
+            exit_code(&outcomes)
+        }
+        Command::Overlay { input, output_dir } => {
+            std::fs::create_dir_all(&output_dir)?;
+            report_written(&process(input.paths(), |path| overlay(path, &output_dir)))
+        }
+        Command::Collage { input, output_dir } => {
+            std::fs::create_dir_all(&output_dir)?;
+            report_written(&process(input.paths(), |path| {
+                tile_collage(path, &output_dir)
+            }))
+        }
+        Command::HarvestTemplates { input, dir } => {
+            std::fs::create_dir_all(&dir)?;
+            exit_code(&process(input.paths(), |path| harvest(&board, path, &dir)))
+        }
+        Command::Watch { dir, interval } => {
+            let mut watcher = Watcher::new(board, dir).interval(Duration::from_millis(interval));
+            watcher.run(|event| {
+                if let Err(err) = &event.result {
+                    eprintln!("{}: {}", event.path.display(), err.message());
+                }
+                if let Some(err) = &event.write_error {
+                    eprintln!("{}: {}", event.path.display(), err);
+                }
+                if let Some(sidecar) = &event.sidecar {
+                    println!("{}", sidecar.display());
+                }
+            })?;
+            0
+        }
+    };
+    Ok(code)
+}
+
+fn main() {
+    env_logger::init();
+    let cli = Cli::parse();
+    let code = match run(cli) {
+        Ok(code) => code,
+        Err(failure) => {
+            eprintln!("{}", failure);
+            failure.kind.exit_code()
+        }
+    };
+    std::process::exit(code);
+}
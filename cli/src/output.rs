@@ -0,0 +1,175 @@
+//! Write recognition results and layouts as text, JSON or CSV.
+use crate::{Failure, Format, Outcome};
+use serde_json::{json, Map, Value};
+use std::io::{self, Write};
+use std::path::Path;
+use wordfeud_ocr::{Layout, Ocr, OcrResults, OcrStats};
+
+/// Write the recognition results of the screenshots.
+///
+/// Text output is the same as the `.expect` files in the library tests.
+/// CSV output has a row for each recognized cell: `path,area,row,col,value,score`.
+/// Failures are only included in JSON output.
+pub fn write_results<W: Write>(
+    out: &mut W,
+    format: Format,
+    outcomes: &[Outcome<OcrResults>],
+) -> io::Result<()> {
+    match format {
+        Format::Text => {
+            for (path, res) in outcomes {
+                if let Ok(res) = res {
+                    write!(
+                        out,
+                        "Screenshot: {}\n\nTiles:\n{}\n\nLetters: {}\n\nGrid:\n{}\n",
+                        path.display(),
+                        res.tiles_ocr,
+                        res.rack_ocr,
+                        res.grid_ocr
+                    )?;
+                }
+            }
+        }
+        Format::Json => write_json(out, outcomes, "result")?,
+        Format::Csv => {
+            writeln!(out, "path,area,row,col,value,score")?;
+            for (path, res) in outcomes {
+                if let Ok(res) = res {
+                    let areas = [
+                        ("tiles", &res.tiles_ocr, &res.tiles_stats),
+                        ("rack", &res.rack_ocr, &res.rack_stats),
+                        ("grid", &res.grid_ocr, &res.grid_stats),
+                    ];
+                    for (area, ocr, stats) in areas.iter() {
+                        write_cells(out, path, area, ocr, stats)?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_cells<W: Write>(
+    out: &mut W,
+    path: &Path,
+    area: &str,
+    ocr: &Ocr,
+    stats: &OcrStats,
+) -> io::Result<()> {
+    let ncols = ocr.first().map_or(1, |row| row.len());
+    for stat in stats {
+        let (row, col) = (stat.index / ncols, stat.index % ncols);
+        writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            csv_field(&path.to_string_lossy()),
+            area,
+            row,
+            col,
+            csv_field(&ocr[row][col]),
+            stat.min_value
+        )?;
+    }
+    Ok(())
+}
+
+/// Write the layouts of the screenshots.
+///
+/// CSV output has a row for each board row and column, and each rack column: `path,area,index,start,end`.
+/// Failures are only included in JSON output.
+pub fn write_layouts<W: Write>(
+    out: &mut W,
+    format: Format,
+    outcomes: &[Outcome<Layout>],
+) -> io::Result<()> {
+    match format {
+        Format::Text => {
+            for (path, res) in outcomes {
+                if let Ok(layout) = res {
+                    writeln!(out, "Screenshot: {}", path.display())?;
+                    writeln!(out, "Board area: {:?}", layout.board_area)?;
+                    writeln!(out, "Rows: {:?}", layout.rows)?;
+                    writeln!(out, "Cols: {:?}", layout.cols)?;
+                    writeln!(out, "Rack area: {:?}", layout.rack_area)?;
+                    writeln!(out, "Rack cols: {:?}\n", layout.rack_cols)?;
+                }
+            }
+        }
+        Format::Json => write_json(out, outcomes, "layout")?,
+        Format::Csv => {
+            writeln!(out, "path,area,index,start,end")?;
+            for (path, res) in outcomes {
+                if let Ok(layout) = res {
+                    let areas = [
+                        ("rows", &layout.rows),
+                        ("cols", &layout.cols),
+                        ("rack_rows", &layout.rack_rows),
+                        ("rack_cols", &layout.rack_cols),
+                    ];
+                    for (area, ranges) in areas.iter() {
+                        for (i, (start, end)) in ranges.iter().enumerate() {
+                            writeln!(
+                                out,
+                                "{},{},{},{},{}",
+                                csv_field(&path.to_string_lossy()),
+                                area,
+                                i,
+                                start,
+                                end
+                            )?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Write a JSON array with an object for each screenshot: `{"path": .., key: ..}` or `{"path": .., "error": ..}`.
+fn write_json<W: Write, T: serde::Serialize>(
+    out: &mut W,
+    outcomes: &[Outcome<T>],
+    key: &str,
+) -> io::Result<()> {
+    let values: Vec<Value> = outcomes
+        .iter()
+        .map(|(path, res)| {
+            let mut value = Map::new();
+            value.insert("path".into(), path.to_string_lossy().into());
+            match res {
+                Ok(res) => value.insert(key.into(), serde_json::to_value(res)?),
+                Err(failure) => value.insert("error".into(), error_json(failure)),
+            };
+            Ok(Value::Object(value))
+        })
+        .collect::<serde_json::Result<_>>()?;
+    serde_json::to_writer_pretty(&mut *out, &values)?;
+    writeln!(out)
+}
+
+fn error_json(failure: &Failure) -> Value {
+    json!({ "kind": failure.kind.as_str(), "message": failure.message })
+}
+
+/// Quote a CSV field if necessary.
+fn csv_field(field: &str) -> String {
+    if field.contains(&[',', '"', '\n'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("a.png"), "a.png");
+        assert_eq!(csv_field("a,b.png"), "\"a,b.png\"");
+        assert_eq!(csv_field("a\"b"), "\"a\"\"b\"");
+    }
+}
@@ -0,0 +1,181 @@
+use std::process::{Command, Output, Stdio};
+use std::time::{Duration, Instant};
+
+/// Run the wordfeud-ocr binary in the library directory, with the screenshots in `tests`.
+fn wordfeud_ocr(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_wordfeud-ocr"))
+        .current_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/../lib"))
+        .args(args)
+        .output()
+        .expect("failed to run wordfeud-ocr")
+}
+
+#[test]
+fn test_recognize_text() {
+    let output = wordfeud_ocr(&["recognize", "tests/screenshot_english.png"]);
+    assert_eq!(output.status.code(), Some(0));
+    let expect = include_str!("../../lib/tests/screenshot_english.expect");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), expect);
+}
+
+#[test]
+fn test_recognize_json() {
+    let output = wordfeud_ocr(&["recognize", "-f", "json", "tests/screenshot_swedish.png"]);
+    assert_eq!(output.status.code(), Some(0));
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let result = &value[0]["result"];
+    assert_eq!(value[0]["path"], "tests/screenshot_swedish.png");
+    assert_eq!(result["tiles_ocr"].as_array().unwrap().len(), 15);
+    assert_eq!(result["board_area"]["width"], 1080);
+}
+
+#[test]
+fn test_glob_csv() {
+    let output = wordfeud_ocr(&["layout", "-f", "csv", "tests/screenshot_dutch_*.png"]);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    assert_eq!(lines.next(), Some("path,area,index,start,end"));
+    let mut paths: Vec<&str> = lines
+        .filter(|line| line.contains(",rows,"))
+        .map(|line| line.split(',').next().unwrap())
+        .collect();
+    paths.dedup();
+    assert_eq!(paths.len(), 5);
+}
+
+#[test]
+fn test_exit_codes() {
+    let output = wordfeud_ocr(&["recognize", "tests/missing.png"]);
+    assert_eq!(output.status.code(), Some(3));
+    let output = wordfeud_ocr(&["recognize", "../images/logo.png"]);
+    assert_eq!(output.status.code(), Some(4));
+    let output = wordfeud_ocr(&["recognize", "Cargo.toml"]);
+    assert_eq!(output.status.code(), Some(5));
+    // the first failure determines the exit code, the other screenshots are still recognized
+    let output = wordfeud_ocr(&[
+        "recognize",
+        "-f",
+        "json",
+        "../images/logo.png",
+        "tests/screenshot_english.png",
+        "tests/missing.png",
+    ]);
+    assert_eq!(output.status.code(), Some(4));
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(value[0]["error"]["kind"], "layout");
+    assert!(value[1]["result"].is_object());
+    assert_eq!(value[2]["error"]["kind"], "io");
+}
+
+#[test]
+fn test_watch() {
+    let dir = std::env::temp_dir().join(format!("wordfeud-ocr-cli-watch-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_wordfeud-ocr"))
+        .args(["watch", "--interval", "50"])
+        .arg(&dir)
+        .stdout(Stdio::null())
+        .spawn()
+        .expect("failed to run wordfeud-ocr");
+    std::fs::copy(
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../lib/tests/screenshot_english.png"
+        ),
+        dir.join("english.png"),
+    )
+    .unwrap();
+    let sidecar = dir.join("english.json");
+    let start = Instant::now();
+    while !sidecar.exists() && start.elapsed() < Duration::from_secs(30) {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    child.kill().unwrap();
+    child.wait().unwrap();
+    let value: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&sidecar).unwrap()).unwrap();
+    assert_eq!(value["rack_ocr"][0].as_array().unwrap().len(), 7);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_dictionary() {
+    let path =
+        std::env::temp_dir().join(format!("wordfeud-ocr-cli-words-{}.txt", std::process::id()));
+    // `hedonic` is recognized, `hedonie` is the only word in the dictionary that fits
+    let words = "if\ndental\nex\nhedonie\nbrent\ngaits\nmunged\nrobotic\nhedonism\nevenly\nvega\nzite\nfax\n";
+    std::fs::write(&path, words).unwrap();
+    let output = wordfeud_ocr(&[
+        "recognize",
+        "--dictionary",
+        path.to_str().unwrap(),
+        "--language",
+        "en",
+        "tests/screenshot_english.png",
+    ]);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("tests/screenshot_english.png: (7, 12) c -> e"));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("......hedonIe.."));
+
+    let output = wordfeud_ocr(&[
+        "recognize",
+        "--dictionary",
+        "missing.txt",
+        "tests/screenshot_english.png",
+    ]);
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn test_solve() {
+    let path =
+        std::env::temp_dir().join(format!("wordfeud-ocr-cli-solve-{}.txt", std::process::id()));
+    std::fs::write(&path, "vega\nzite\nfax\n").unwrap();
+    let output = wordfeud_ocr(&[
+        "solve",
+        "--dictionary",
+        path.to_str().unwrap(),
+        "--language",
+        "en",
+        "tests/screenshot_english.png",
+    ]);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout,
+        "Screenshot: tests/screenshot_english.png\nvega (11, 10) horizontal 11\n"
+    );
+}
+
+#[test]
+fn test_gcg() {
+    // the same screenshot twice has no moves
+    let output = wordfeud_ocr(&[
+        "gcg",
+        "tests/screenshot_english.png",
+        "tests/screenshot_english.png",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(
+        stdout,
+        "#character-encoding UTF-8\n#player1 me Me\n#player2 opponent Opponent\n"
+    );
+
+    // screenshots of different games
+    let output = wordfeud_ocr(&[
+        "gcg",
+        "--language",
+        "nl",
+        "tests/screenshot_dutch_1.png",
+        "tests/screenshot_dutch_2.png",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.starts_with("Screenshot 1 does not follow the screenshot before it\n"));
+}
@@ -0,0 +1,111 @@
+//! Benchmarks for segmentation and recognition of the test screenshots.
+//!
+//! Each phase of the recognition is measured separately for every screenshot:
+//! ```text
+//! cargo bench --bench recognize
+//! ```
+//! With the `rayon` feature, the `parallel` group compares the recognition in a pool with a single
+//! thread, which is sequential, with the recognition in the default pool:
+//! ```text
+//! cargo bench --bench recognize --features rayon -- parallel
+//! ```
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use image::GrayImage;
+use wordfeud_ocr::{Board, Layout};
+
+const SCREENSHOTS: &[&str] = &[
+    "screenshot_english",
+    "screenshot_dutch",
+    "screenshot_dutch_1",
+    "screenshot_dutch_2",
+    "screenshot_dutch_3",
+    "screenshot_dutch_4",
+    "screenshot_dutch_5",
+    "screenshot_swedish",
+];
+
+fn screenshots() -> Vec<(&'static str, GrayImage)> {
+    SCREENSHOTS
+        .iter()
+        .map(|name| {
+            let path = format!("tests/{}.png", name);
+            (*name, image::open(&path).unwrap().into_luma8())
+        })
+        .collect()
+}
+
+fn layout(c: &mut Criterion) {
+    let mut group = c.benchmark_group("layout");
+    group.sample_size(20);
+    for (name, gray) in screenshots() {
+        group.bench_with_input(BenchmarkId::new("new", name), &gray, |b, gray| {
+            b.iter(|| Layout::new(gray))
+        });
+        group.bench_with_input(BenchmarkId::new("segment", name), &gray, |b, gray| {
+            b.iter_batched(
+                || Layout::new(gray),
+                |layout| layout.segment().unwrap(),
+                criterion::BatchSize::LargeInput,
+            )
+        });
+        let layout = Layout::new(&gray).segment().unwrap();
+        let cells = Layout::get_cells(&layout.rows, &layout.cols);
+        group.bench_with_input(
+            BenchmarkId::new("get_tile_index", name),
+            &cells,
+            |b, cells| b.iter(|| layout.get_tile_index(cells)),
+        );
+    }
+    group.finish();
+}
+
+fn recognize(c: &mut Criterion) {
+    let board = Board::new();
+    let mut group = c.benchmark_group("recognize");
+    group.sample_size(10);
+    for (name, gray) in screenshots() {
+        let layout = Layout::new(&gray).segment().unwrap();
+        group.bench_with_input(BenchmarkId::new("tiles", name), &gray, |b, gray| {
+            b.iter(|| board.recognize_tiles(gray, &layout))
+        });
+        group.bench_with_input(BenchmarkId::new("board", name), &gray, |b, gray| {
+            b.iter(|| board.recognize_board(gray, &layout))
+        });
+        group.bench_with_input(BenchmarkId::new("rack", name), &gray, |b, gray| {
+            b.iter(|| board.recognize_rack(gray, &layout))
+        });
+        group.bench_with_input(BenchmarkId::new("screenshot", name), &gray, |b, gray| {
+            b.iter(|| board.recognize_screenshot(gray).unwrap())
+        });
+    }
+    group.finish();
+}
+
+#[cfg(feature = "rayon")]
+fn parallel(c: &mut Criterion) {
+    let board = Board::new();
+    let pools = [
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .unwrap(),
+        rayon::ThreadPoolBuilder::new().build().unwrap(),
+    ];
+    let mut group = c.benchmark_group("parallel");
+    group.sample_size(10);
+    for (name, gray) in screenshots() {
+        for pool in &pools {
+            let threads = format!("{} threads", pool.current_num_threads());
+            group.bench_with_input(BenchmarkId::new(threads, name), &gray, |b, gray| {
+                b.iter(|| pool.install(|| board.recognize_screenshot(gray).unwrap()))
+            });
+        }
+    }
+    group.finish();
+}
+
+#[cfg(feature = "rayon")]
+criterion_group!(benches, layout, recognize, parallel);
+#[cfg(not(feature = "rayon"))]
+criterion_group!(benches, layout, recognize);
+criterion_main!(benches);
@@ -0,0 +1,182 @@
+use crate::{Board, Error, OcrResults};
+use log::warn;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// The result of recognizing a single screenshot in a batch
+#[derive(Debug)]
+pub struct BatchItem {
+    /// The path of the screenshot
+    pub path: PathBuf,
+    /// The recognized screenshot, or the reason why it failed
+    pub result: Result<OcrResults, Error>,
+    /// The time it took to load and recognize the screenshot
+    pub duration: Duration,
+}
+
+/// The results of [recognize_batch](crate::Board::recognize_batch): a result for each screenshot, and a summary report.
+#[derive(Debug)]
+pub struct BatchResults {
+    /// The results, in the order of the paths
+    pub items: Vec<BatchItem>,
+    /// The summary of the results
+    pub report: BatchReport,
+}
+
+/// Summary of a batch: the success rate, the failure reasons and the recognition times.
+#[derive(Debug, Clone, Default)]
+pub struct BatchReport {
+    /// The number of screenshots in the batch
+    pub total: usize,
+    /// The number of recognized screenshots
+    pub succeeded: usize,
+    /// The number of failures for each [reason](crate::Error::reason), e.g. `LayoutFailed(LookForRack)`
+    pub failures: BTreeMap<String, usize>,
+    /// The recognition times of all screenshots
+    pub timings: Timings,
+}
+
+/// Percentiles of the time per screenshot
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Timings {
+    /// The fastest screenshot
+    pub min: Duration,
+    /// The 50th percentile
+    pub median: Duration,
+    /// The 90th percentile
+    pub p90: Duration,
+    /// The 99th percentile
+    pub p99: Duration,
+    /// The slowest screenshot
+    pub max: Duration,
+    /// The total time for all screenshots
+    pub total: Duration,
+}
+
+impl Timings {
+    /// Compute the percentiles of `durations` with the nearest rank method.
+    pub fn new(durations: &[Duration]) -> Timings {
+        if durations.is_empty() {
+            return Timings::default();
+        }
+        let mut sorted = durations.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let percentile = |p: usize| sorted[((p * n + 99) / 100).clamp(1, n) - 1];
+        Timings {
+            min: sorted[0],
+            median: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: sorted[n - 1],
+            total: sorted.iter().sum(),
+        }
+    }
+}
+
+impl BatchReport {
+    /// Create the report for the results of a batch.
+    pub fn new(items: &[BatchItem]) -> BatchReport {
+        let mut failures = BTreeMap::new();
+        for item in items {
+            if let Err(err) = &item.result {
+                *failures.entry(err.reason()).or_insert(0) += 1;
+            }
+        }
+        let durations: Vec<Duration> = items.iter().map(|item| item.duration).collect();
+        BatchReport {
+            total: items.len(),
+            succeeded: items.iter().filter(|item| item.result.is_ok()).count(),
+            failures,
+            timings: Timings::new(&durations),
+        }
+    }
+
+    /// The fraction of recognized screenshots, or 0 for an empty batch.
+    pub fn success_rate(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.succeeded as f64 / self.total as f64
+        }
+    }
+}
+
+impl fmt::Display for BatchReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Recognized {} of {} screenshots ({:.1}%)",
+            self.succeeded,
+            self.total,
+            100.0 * self.success_rate()
+        )?;
+        for (reason, count) in &self.failures {
+            writeln!(f, "  {}: {}", reason, count)?;
+        }
+        let t = &self.timings;
+        write!(
+            f,
+            "Time per screenshot: min {:.1?}, median {:.1?}, p90 {:.1?}, p99 {:.1?}, max {:.1?}, total {:.1?}",
+            t.min, t.median, t.p90, t.p99, t.max, t.total
+        )
+    }
+}
+
+impl Board {
+    /// Recognize a batch of screenshot files.
+    ///
+    /// A screenshot that can not be loaded or recognized does not stop the batch: its error is
+    /// returned in its [BatchItem], and counted in the [BatchReport].
+    ///
+    /// # Example
+    /// ```
+    /// # use wordfeud_ocr::Board;
+    /// let board = Board::new();
+    /// let batch = board.recognize_batch(&["tests/screenshot_english.png", "tests/missing.png"]);
+    /// assert_eq!(batch.report.succeeded, 1);
+    /// assert_eq!(batch.report.failures["ImageError"], 1);
+    /// ```
+    pub fn recognize_batch<P: AsRef<Path>>(&self, paths: &[P]) -> BatchResults {
+        let items: Vec<BatchItem> = paths
+            .iter()
+            .map(|path| {
+                let path = path.as_ref();
+                let now = Instant::now();
+                let result = self.recognize_path(path);
+                if let Err(err) = &result {
+                    warn!("{}: {}", path.display(), err);
+                }
+                BatchItem {
+                    path: path.to_path_buf(),
+                    result,
+                    duration: now.elapsed(),
+                }
+            })
+            .collect();
+        let report = BatchReport::new(&items);
+        BatchResults { items, report }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timings() {
+        let durations: Vec<Duration> = (1..=200).rev().map(Duration::from_millis).collect();
+        let t = Timings::new(&durations);
+        assert_eq!(t.min, Duration::from_millis(1));
+        assert_eq!(t.median, Duration::from_millis(100));
+        assert_eq!(t.p90, Duration::from_millis(180));
+        assert_eq!(t.p99, Duration::from_millis(198));
+        assert_eq!(t.max, Duration::from_millis(200));
+        assert_eq!(t.total, Duration::from_millis(200 * 201 / 2));
+        assert_eq!(Timings::new(&[]), Timings::default());
+        let t = Timings::new(&[Duration::from_millis(7)]);
+        assert_eq!((t.min, t.median, t.p99), (t.max, t.max, t.max));
+    }
+}
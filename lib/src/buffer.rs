@@ -0,0 +1,181 @@
+use crate::Error;
+use image::{GenericImageView, Luma};
+
+/// The pixel format of a screenshot buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// 8 bit grayscale
+    Gray8,
+    /// 8 bit red, green, blue, alpha
+    Rgba8,
+    /// 8 bit blue, green, red, alpha, as delivered by Android screen capture
+    Bgra8,
+    /// 8 bit red, green, blue
+    Rgb8,
+    /// 8 bit blue, green, red, as used by OpenCV
+    Bgr8,
+}
+
+impl PixelFormat {
+    /// The number of bytes per pixel
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Gray8 => 1,
+            PixelFormat::Rgb8 | PixelFormat::Bgr8 => 3,
+            PixelFormat::Rgba8 | PixelFormat::Bgra8 => 4,
+        }
+    }
+
+    /// The number of bytes of a buffer with `height` rows of `width` pixels and `stride` bytes per row.
+    /// The last row needs no padding. Returns `None` if the size overflows.
+    pub fn buffer_len(self, width: u32, height: u32, stride: usize) -> Option<usize> {
+        let row = (width as usize).checked_mul(self.bytes_per_pixel())?;
+        match height as usize {
+            0 => Some(0),
+            h => (h - 1).checked_mul(stride)?.checked_add(row),
+        }
+    }
+}
+
+/// A screenshot in a borrowed pixel buffer.
+///
+/// The pixels are not copied. Color pixels are converted to grayscale when they are read, with
+/// the same conversion as [DynamicImage::into_luma8](image::DynamicImage::into_luma8).
+///
+/// # Example
+/// ```
+/// # use wordfeud_ocr::{ScreenBuffer, PixelFormat, Error};
+/// let pixels = vec![0u8; 4 * 1080 * 1920];
+/// let screenshot = ScreenBuffer::new(&pixels, 1080, 1920, 4 * 1080, PixelFormat::Bgra8)?;
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ScreenBuffer<'a> {
+    pixels: &'a [u8],
+    width: u32,
+    height: u32,
+    stride: usize,
+    format: PixelFormat,
+}
+
+impl<'a> ScreenBuffer<'a> {
+    /// Create a screenshot from `pixels`, with `stride` bytes per row.
+    ///
+    /// # Errors
+    /// If the stride is smaller than a row of pixels, or the buffer is too small.
+    pub fn new(
+        pixels: &'a [u8],
+        width: u32,
+        height: u32,
+        stride: usize,
+        format: PixelFormat,
+    ) -> Result<ScreenBuffer<'a>, Error> {
+        let row = width as usize * format.bytes_per_pixel();
+        if stride < row {
+            return Err(Error::InvalidBuffer(format!(
+                "stride {} is less than {} bytes per row",
+                stride, row
+            )));
+        }
+        let len = format
+            .buffer_len(width, height, stride)
+            .ok_or_else(|| Error::InvalidBuffer(String::from("buffer size overflows")))?;
+        if pixels.len() < len {
+            return Err(Error::InvalidBuffer(format!(
+                "buffer has {} bytes, expected at least {}",
+                pixels.len(),
+                len
+            )));
+        }
+        Ok(ScreenBuffer {
+            pixels,
+            width,
+            height,
+            stride,
+            format,
+        })
+    }
+}
+
+impl<'a> GenericImageView for ScreenBuffer<'a> {
+    type Pixel = Luma<u8>;
+    type InnerImageView = Self;
+
+    fn dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn bounds(&self) -> (u32, u32, u32, u32) {
+        (0, 0, self.width, self.height)
+    }
+
+    fn get_pixel(&self, x: u32, y: u32) -> Luma<u8> {
+        assert!(x < self.width && y < self.height, "pixel out of bounds");
+        let i = y as usize * self.stride + x as usize * self.format.bytes_per_pixel();
+        let p = &self.pixels[i..];
+        match self.format {
+            PixelFormat::Gray8 => Luma([p[0]]),
+            PixelFormat::Rgba8 | PixelFormat::Rgb8 => Luma([luma(p[0], p[1], p[2])]),
+            PixelFormat::Bgra8 | PixelFormat::Bgr8 => Luma([luma(p[2], p[1], p[0])]),
+        }
+    }
+
+    fn inner(&self) -> &Self::InnerImageView {
+        self
+    }
+}
+
+/// Convert a color pixel to grayscale, with the same (sRGB) weights as the image crate.
+fn luma(r: u8, g: u8, b: u8) -> u8 {
+    (0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{DynamicImage, Rgba, RgbaImage};
+
+    #[test]
+    fn test_color_conversion() {
+        let rgba = RgbaImage::from_fn(7, 5, |x, y| {
+            Rgba([(x * 40) as u8, (y * 60) as u8, (x * y * 9) as u8, 255])
+        });
+        let gray = DynamicImage::ImageRgba8(rgba.clone()).into_luma8();
+        let mut bgra = rgba.clone();
+        bgra.pixels_mut().for_each(|p| p.0.swap(0, 2));
+        let rgb: Vec<u8> = rgba.pixels().flat_map(|p| p.0[..3].to_vec()).collect();
+        let bgr: Vec<u8> = bgra.pixels().flat_map(|p| p.0[..3].to_vec()).collect();
+        // pad the rows to test the stride
+        let padded: Vec<u8> = rgba
+            .as_raw()
+            .chunks(7 * 4)
+            .flat_map(|row| row.iter().cloned().chain(vec![0; 8]))
+            .collect();
+
+        let views = [
+            ScreenBuffer::new(gray.as_raw(), 7, 5, 7, PixelFormat::Gray8).unwrap(),
+            ScreenBuffer::new(rgba.as_raw(), 7, 5, 28, PixelFormat::Rgba8).unwrap(),
+            ScreenBuffer::new(bgra.as_raw(), 7, 5, 28, PixelFormat::Bgra8).unwrap(),
+            ScreenBuffer::new(&padded, 7, 5, 36, PixelFormat::Rgba8).unwrap(),
+            ScreenBuffer::new(&rgb, 7, 5, 21, PixelFormat::Rgb8).unwrap(),
+            ScreenBuffer::new(&bgr, 7, 5, 21, PixelFormat::Bgr8).unwrap(),
+        ];
+        for view in views.iter() {
+            for (x, y, p) in gray.enumerate_pixels() {
+                assert_eq!(view.get_pixel(x, y), *p);
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_buffer() {
+        let pixels = vec![0u8; 100];
+        assert!(ScreenBuffer::new(&pixels, 10, 10, 10, PixelFormat::Gray8).is_ok());
+        assert!(ScreenBuffer::new(&pixels, 10, 10, 9, PixelFormat::Gray8).is_err());
+        assert!(ScreenBuffer::new(&pixels, 10, 10, 40, PixelFormat::Rgba8).is_err());
+        // the last row has no padding
+        assert_eq!(PixelFormat::Rgba8.buffer_len(10, 3, 48), Some(2 * 48 + 40));
+        assert!(ScreenBuffer::new(&pixels[..95], 5, 10, 10, PixelFormat::Gray8).is_ok());
+        assert_eq!(PixelFormat::Gray8.buffer_len(1, 3, usize::MAX), None);
+    }
+}
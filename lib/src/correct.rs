@@ -0,0 +1,306 @@
+use crate::{Language, Ocr, OcrResults, Word};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+/// The maximum number of tiles that are changed to correct a word
+const MAX_CHANGES: usize = 2;
+
+/// A word list, used to check the words on the board
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    words: HashSet<String>,
+}
+
+impl Dictionary {
+    /// Create a dictionary from `words`. The words are converted to lower case.
+    pub fn new<I, S>(words: I) -> Dictionary
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let words = words
+            .into_iter()
+            .map(|word| word.as_ref().trim().to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+        Dictionary { words }
+    }
+
+    /// Load a dictionary from a word list file, with one word per line.
+    ///
+    /// # Errors
+    /// * The file can not be read, or is not valid UTF-8.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Dictionary> {
+        let text = std::fs::read_to_string(path)?;
+        Ok(Dictionary::new(text.lines()))
+    }
+
+    /// Return true if the dictionary contains `word`
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.to_lowercase())
+    }
+
+    /// The number of words in the dictionary
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Return true if the dictionary has no words
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// The words, in lower case
+    #[cfg(feature = "solver")]
+    pub(crate) fn words(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().map(String::as_str)
+    }
+}
+
+/// A tile on the board that was changed to correct a word
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Correction {
+    /// The row of the tile
+    pub row: usize,
+    /// The column of the tile
+    pub col: usize,
+    /// The recognized tile
+    pub before: String,
+    /// The corrected tile
+    pub after: String,
+    /// The cost of the correction: how much worse the template of the corrected tile matched
+    pub cost: f32,
+}
+
+impl fmt::Display for Correction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "({}, {}) {} -> {} (cost {:.3})",
+            self.row, self.col, self.before, self.after, self.cost
+        )
+    }
+}
+
+/// The result of [correct_words](crate::OcrResults::correct_words)
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Corrections {
+    /// The corrected tiles
+    pub corrections: Vec<Correction>,
+    /// The total cost of the corrections
+    pub cost: f32,
+    /// The words that are not in the dictionary after the corrections
+    pub invalid: Vec<Word>,
+}
+
+/// The alternative tiles for each cell, with the cost to use them
+type Alternatives = HashMap<(usize, usize), Vec<(String, f32)>>;
+
+/// A change of the tile at `(row, col)`, with its cost
+type Change = (usize, usize, String, f32);
+
+impl OcrResults {
+    /// Correct OCR errors in the tiles on the board, using the words in `dictionary`.
+    ///
+    /// Each word that is not in the dictionary is corrected with the alternative template matches of its tiles,
+    /// see [OcrStat::alternatives](crate::OcrStat::alternatives). Alternatives that are not a tile of `language`
+    /// are skipped. The correction with the minimal cost that makes
+    /// the word and all the words that cross the changed tiles valid is applied to
+    /// [tiles_ocr](crate::OcrResults::tiles_ocr). At most 2 tiles are changed for a word. The cost of a change
+    /// is the difference between the match errors of the alternative and the recognized tile.
+    ///
+    /// The stats are not changed, they hold the original recognition.
+    pub fn correct_words(&mut self, dictionary: &Dictionary, language: Language) -> Corrections {
+        let letters: HashSet<&str> = language.tiles().iter().map(|tile| tile.letter).collect();
+        let alternatives: Alternatives = self
+            .tiles_stats
+            .iter()
+            .map(|stat| {
+                let costs = stat
+                    .alternatives
+                    .iter()
+                    .filter(|(tag, _)| letters.contains(tag.to_lowercase().as_str()))
+                    .map(|(tag, value)| (tag.clone(), (value - stat.min_value).max(0.0)))
+                    .collect();
+                ((stat.index / 15, stat.index % 15), costs)
+            })
+            .collect();
+
+        let mut result = Corrections::default();
+        for word in self.tiles_ocr.words() {
+            // an earlier correction can have corrected the word
+            let current = word_at(&self.tiles_ocr, &word);
+            if dictionary.contains(&current) {
+                continue;
+            }
+            if let Some(changes) =
+                best_correction(&self.tiles_ocr, &word, &alternatives, dictionary)
+            {
+                for (row, col, after, cost) in changes {
+                    let before = std::mem::replace(&mut self.tiles_ocr[row][col], after.clone());
+                    result.cost += cost;
+                    result.corrections.push(Correction {
+                        row,
+                        col,
+                        before,
+                        after,
+                        cost,
+                    });
+                }
+            }
+        }
+        result.invalid = self
+            .tiles_ocr
+            .words()
+            .into_iter()
+            .filter(|word| !dictionary.contains(&word.text()))
+            .collect();
+        result
+    }
+}
+
+/// The text of the tiles of `word` in `grid`
+fn word_at(grid: &Ocr, word: &Word) -> String {
+    word.cells()
+        .map(|(row, col)| grid[row][col].to_lowercase())
+        .collect()
+}
+
+/// Find the cheapest set of tile changes that makes `word` and the words that cross the changed tiles valid.
+///
+/// Returns the changes, or `None` if no valid correction was found.
+fn best_correction(
+    grid: &Ocr,
+    word: &Word,
+    alternatives: &Alternatives,
+    dictionary: &Dictionary,
+) -> Option<Vec<Change>> {
+    // all single tile changes in the word
+    let mut options = Vec::new();
+    for (row, col) in word.cells() {
+        let tile = &grid[row][col];
+        let wildcard = *tile != tile.to_lowercase();
+        for (tag, cost) in alternatives.get(&(row, col)).into_iter().flatten() {
+            let after = if wildcard {
+                tag.to_uppercase()
+            } else {
+                tag.to_lowercase()
+            };
+            options.push((row, col, after, *cost));
+        }
+    }
+
+    let mut best: Option<(f32, Vec<usize>)> = None;
+    let mut candidates: Vec<Vec<usize>> = (0..options.len()).map(|i| vec![i]).collect();
+    for changes in 1..=MAX_CHANGES {
+        if changes > 1 {
+            candidates = extend(&candidates, &options);
+        }
+        for set in &candidates {
+            let cost: f32 = set.iter().map(|&i| options[i].3).sum();
+            if best.as_ref().map_or(false, |(best, _)| cost >= *best) {
+                continue;
+            }
+            let mut grid = grid.clone();
+            for &i in set {
+                let (row, col, ref after, _) = options[i];
+                grid[row][col] = after.clone();
+            }
+            let changed: HashSet<(usize, usize)> =
+                set.iter().map(|&i| (options[i].0, options[i].1)).collect();
+            let valid = grid
+                .words()
+                .iter()
+                .filter(|w| w.cells().any(|cell| changed.contains(&cell)))
+                .all(|w| dictionary.contains(&w.text()));
+            if valid {
+                best = Some((cost, set.clone()));
+            }
+        }
+    }
+    best.map(|(_, set)| set.into_iter().map(|i| options[i].clone()).collect())
+}
+
+/// Extend each set of changes with a change of a later tile
+fn extend(sets: &[Vec<usize>], options: &[Change]) -> Vec<Vec<usize>> {
+    let mut extended = Vec::new();
+    for set in sets {
+        let last = &options[set[set.len() - 1]];
+        for (j, option) in options.iter().enumerate() {
+            if (option.0, option.1) > (last.0, last.1) {
+                let mut set = set.clone();
+                set.push(j);
+                extended.push(set);
+            }
+        }
+    }
+    extended
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::results;
+    use crate::OcrStat;
+
+    fn stat(index: usize, tag: &str, alternatives: &[(&str, f32)]) -> OcrStat {
+        OcrStat {
+            index,
+            tag: tag.to_string(),
+            min_value: 0.1,
+            min_value_location: (0, 0),
+            alternatives: alternatives
+                .iter()
+                .map(|&(tag, value)| (tag.to_string(), value))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_correct_words() {
+        // "qat" across, "tea" down
+        let mut rows = vec!["..............."; 7];
+        rows.extend(&["......qat......", "........e......", "........A......"]);
+        let mut res = results(&rows, "", &[]);
+        res.tiles_stats = vec![
+            stat(7 * 15 + 6, "Q", &[("Ä", 0.12), ("O", 0.15), ("C", 0.3)]),
+            stat(7 * 15 + 7, "A", &[("R", 0.4)]),
+            stat(9 * 15 + 8, "A", &[("R", 0.12)]),
+        ];
+        let mut swedish = res.clone();
+        let dictionary = Dictionary::new(["äat", "oat", "cat", "tea", "TEAR"].iter());
+        // Ä is not an English tile
+        let corrections = res.correct_words(&dictionary, Language::English);
+        assert_eq!(corrections.corrections.len(), 1);
+        let correction = &corrections.corrections[0];
+        assert_eq!(
+            (
+                correction.row,
+                correction.col,
+                correction.before.as_str(),
+                correction.after.as_str()
+            ),
+            (7, 6, "q", "o")
+        );
+        assert!((corrections.cost - 0.05).abs() < 1e-6);
+        assert!(corrections.invalid.is_empty());
+        assert_eq!(res.tiles_ocr[7][6], "o");
+        // the wildcard stays a wildcard
+        assert_eq!(res.tiles_ocr[9][8], "A");
+        let corrections = swedish.correct_words(&dictionary, Language::Swedish);
+        assert_eq!(corrections.corrections[0].after, "ä");
+
+        // no correction for a word without alternatives
+        let dictionary = Dictionary::new(["oat"].iter());
+        let corrections = res.correct_words(&dictionary, Language::English);
+        assert!(corrections.corrections.is_empty());
+        assert_eq!(corrections.invalid.len(), 1);
+        assert_eq!(corrections.invalid[0].text(), "tea");
+    }
+}
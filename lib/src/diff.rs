@@ -0,0 +1,260 @@
+use crate::{Direction, Language, Location, MoveError, MoveScore, OcrResults, Word};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::fmt;
+
+/// A tile that is placed on the board between two screenshots
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct PlacedTile {
+    /// The row of the tile
+    pub row: usize,
+    /// The column of the tile
+    pub col: usize,
+    /// The letter of the tile, in lower case
+    pub tile: String,
+    /// True if the tile is a blank tile that is used as a wildcard
+    pub wildcard: bool,
+}
+
+/// A difference between two screenshots of a game that is not possible with a move, which indicates an OCR error
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
+pub enum Inconsistency {
+    /// A tile of the first screenshot is missing in the second screenshot
+    Removed { location: Location, tile: String },
+    /// A tile has another letter in the second screenshot, or changed from or to a wildcard
+    Changed {
+        location: Location,
+        before: String,
+        after: String,
+    },
+    /// The new tiles are not in one row or column, or there is a gap between them
+    Scattered { locations: Vec<Location> },
+    /// The new tiles are not a legal move on the board of the first screenshot
+    IllegalMove { error: MoveError },
+}
+
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Inconsistency::Removed { location, tile } => {
+                write!(f, "The tile '{}' at {} is removed", tile, location)
+            }
+            Inconsistency::Changed {
+                location,
+                before,
+                after,
+            } => write!(
+                f,
+                "The tile at {} changed from '{}' to '{}'",
+                location, before, after
+            ),
+            Inconsistency::Scattered { locations } => {
+                write!(f, "The new tiles are not in one word:")?;
+                for location in locations {
+                    write!(f, " {}", location)?;
+                }
+                Ok(())
+            }
+            Inconsistency::IllegalMove { error } => write!(f, "Illegal move: {}", error),
+        }
+    }
+}
+
+/// The move between two screenshots of a game, see [diff](OcrResults::diff)
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct MoveDiff {
+    /// The new tiles on the board
+    pub tiles: Vec<PlacedTile>,
+    /// The words with a new tile
+    pub words: Vec<Word>,
+    /// The score of the move, if the new tiles are a legal move
+    pub score: Option<MoveScore>,
+    /// The differences that are not possible with a move
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+impl OcrResults {
+    /// Compute the move between this screenshot and a `later` screenshot of the same game.
+    ///
+    /// The new tiles are the tiles of `later` on empty cells of this board. Their word is scored on this
+    /// board with [score_move](OcrResults::score_move), with the letter values of `language`.
+    /// Tiles that are removed or changed, new tiles that do not form one word, and an illegal move are
+    /// reported as [inconsistencies](Inconsistency).
+    pub fn diff(&self, later: &OcrResults, language: Language) -> MoveDiff {
+        let mut diff = MoveDiff::default();
+        for (row, (before, after)) in self
+            .tiles_ocr
+            .iter()
+            .zip(later.tiles_ocr.iter())
+            .enumerate()
+        {
+            for (col, (before, after)) in before.iter().zip(after.iter()).enumerate() {
+                let location = Location::Board { row, col };
+                match (before.as_str(), after.as_str()) {
+                    (b, a) if b == a => {}
+                    (".", _) => diff.tiles.push(PlacedTile {
+                        row,
+                        col,
+                        tile: after.to_lowercase(),
+                        wildcard: *after != after.to_lowercase(),
+                    }),
+                    (_, ".") => diff.inconsistencies.push(Inconsistency::Removed {
+                        location,
+                        tile: before.clone(),
+                    }),
+                    _ => diff.inconsistencies.push(Inconsistency::Changed {
+                        location,
+                        before: before.clone(),
+                        after: after.clone(),
+                    }),
+                }
+            }
+        }
+        if diff.tiles.is_empty() {
+            return diff;
+        }
+
+        let cells: Vec<(usize, usize)> = diff.tiles.iter().map(|t| (t.row, t.col)).collect();
+        diff.words = later
+            .words()
+            .into_iter()
+            .filter(|word| word.cells().any(|cell| cells.contains(&cell)))
+            .collect();
+        match main_word(&diff.words, &cells) {
+            Some(word) => match self.score_move(word, language) {
+                Ok(score) => diff.score = Some(score),
+                Err(error) => diff
+                    .inconsistencies
+                    .push(Inconsistency::IllegalMove { error }),
+            },
+            // a single tile without tiles next to it
+            None if cells.len() == 1 => diff.inconsistencies.push(Inconsistency::IllegalMove {
+                error: MoveError::NotConnected,
+            }),
+            None => diff.inconsistencies.push(Inconsistency::Scattered {
+                locations: cells
+                    .iter()
+                    .map(|&(row, col)| Location::Board { row, col })
+                    .collect(),
+            }),
+        }
+        diff
+    }
+}
+
+/// Find the word with all the new tiles. A single tile is played horizontally if it forms a horizontal word.
+fn main_word<'a>(words: &'a [Word], cells: &[(usize, usize)]) -> Option<&'a Word> {
+    let same_row = cells.iter().all(|&(row, _)| row == cells[0].0);
+    let same_col = cells.iter().all(|&(_, col)| col == cells[0].1);
+    words.iter().find(|word| {
+        let direction_ok = match word.direction {
+            Direction::Horizontal => same_row,
+            Direction::Vertical => same_col,
+        };
+        direction_ok && cells.iter().all(|cell| word.cells().any(|c| c == *cell))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::results;
+
+    #[test]
+    fn test_diff() {
+        let mut rows = vec!["..............."; 7];
+        rows.push("......qat......");
+        let before = results(&rows, "", &[(8, 8, "2w")]);
+        rows.push("........Ex.....");
+        let after = results(&rows, "", &[(8, 8, "2w")]);
+        let diff = before.diff(&after, Language::English);
+        assert_eq!(
+            diff.tiles,
+            [
+                PlacedTile {
+                    row: 8,
+                    col: 8,
+                    tile: String::from("e"),
+                    wildcard: true
+                },
+                PlacedTile {
+                    row: 8,
+                    col: 9,
+                    tile: String::from("x"),
+                    wildcard: false
+                }
+            ]
+        );
+        let words: Vec<String> = diff.words.iter().map(|w| w.to_string()).collect();
+        assert_eq!(words, ["Ex", "tE"]);
+        // Ex and tE on a double word square, the wildcard scores 0
+        let score = diff.score.unwrap();
+        assert_eq!(score.words[0].0.to_string(), "Ex");
+        assert_eq!(score.total, 8 * 2 + 2);
+        assert!(diff.inconsistencies.is_empty());
+
+        // no move
+        let diff = before.diff(&before, Language::English);
+        assert_eq!(diff, MoveDiff::default());
+    }
+
+    #[test]
+    fn test_inconsistencies() {
+        let mut rows = vec!["..............."; 7];
+        rows.push("......qat......");
+        let before = results(&rows, "", &[]);
+        rows[7] = "......qot......";
+        rows.push("..x......a.....");
+        let after = results(&rows, "", &[]);
+        let diff = before.diff(&after, Language::English);
+        assert_eq!(diff.tiles.len(), 2);
+        assert_eq!(
+            diff.inconsistencies,
+            [
+                Inconsistency::Changed {
+                    location: Location::Board { row: 7, col: 7 },
+                    before: String::from("a"),
+                    after: String::from("o")
+                },
+                Inconsistency::Scattered {
+                    locations: vec![
+                        Location::Board { row: 8, col: 2 },
+                        Location::Board { row: 8, col: 9 }
+                    ]
+                }
+            ]
+        );
+        assert!(diff.score.is_none());
+
+        rows[7] = "......q.t......";
+        rows[8] = "...............";
+        rows[2] = "...ab..........";
+        let diff = before.diff(&results(&rows, "", &[]), Language::English);
+        assert_eq!(
+            diff.inconsistencies[0].to_string(),
+            "The tile 'a' at board (7, 7) is removed"
+        );
+        assert_eq!(
+            diff.inconsistencies[1],
+            Inconsistency::IllegalMove {
+                error: MoveError::NotConnected
+            }
+        );
+
+        // a single tile that is not next to a tile on the board
+        rows[7] = "......qat......";
+        rows[2] = "...a...........";
+        let diff = before.diff(&results(&rows, "", &[]), Language::English);
+        assert_eq!(diff.tiles.len(), 1);
+        assert_eq!(
+            diff.inconsistencies,
+            [Inconsistency::IllegalMove {
+                error: MoveError::NotConnected
+            }]
+        );
+    }
+}
@@ -3,6 +3,7 @@ use thiserror::Error;
 
 /// Errors that can occur when recognizing the board
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum Error {
     /// The detected board is not square
     #[error("Board not square {0}")]
@@ -10,7 +11,34 @@ pub enum Error {
     /// The board could not be segmented
     #[error("Failed to create layout")]
     LayoutFailed(Segment),
+    /// The screenshot buffer does not match its dimensions and pixel format
+    #[error("Invalid screenshot buffer: {0}")]
+    InvalidBuffer(String),
     /// An error from the [image](https://github.com/image-rs/image) library
     #[error("Image error")]
     ImageError(#[from] image::error::ImageError),
 }
+
+impl Error {
+    /// The reason of the failure, without details: the name of the variant, and the segmentation state
+    /// for a layout failure. E.g. `LayoutFailed(LookForRack)`.
+    pub fn reason(&self) -> String {
+        match self {
+            Error::BoardNotSquare(_) => String::from("BoardNotSquare"),
+            Error::LayoutFailed(state) => format!("LayoutFailed({})", state.name()),
+            Error::InvalidBuffer(_) => String::from("InvalidBuffer"),
+            Error::ImageError(_) => String::from("ImageError"),
+        }
+    }
+
+    /// The message of the error with its sources, e.g. `Image error: Format error decoding Png: ..`.
+    pub fn message(&self) -> String {
+        let mut message = self.to_string();
+        let mut source = std::error::Error::source(self);
+        while let Some(err) = source {
+            message = format!("{}: {}", message, err);
+            source = err.source();
+        }
+        message
+    }
+}
@@ -0,0 +1,559 @@
+use crate::{Direction, Inconsistency, Language, Ocr, OcrResults, Word};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::fmt::Write;
+use thiserror::Error;
+
+/// The column letters of the board in GCG positions
+const COLUMNS: &str = "ABCDEFGHIJKLMNO";
+
+/// Errors for reading, writing and replaying game records
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum GcgError {
+    /// A screenshot is not a legal move after the screenshot before it
+    #[error("Screenshot {screenshot} does not follow the screenshot before it")]
+    Inconsistent {
+        screenshot: usize,
+        inconsistencies: Vec<Inconsistency>,
+    },
+    /// A line of a GCG file can not be parsed
+    #[error("Line {line}: {message}")]
+    Parse { line: usize, message: String },
+    /// A move places a tile on another tile, or does not fit on the board
+    #[error("Move {index} does not fit on the board at ({row}, {col})")]
+    Conflict {
+        index: usize,
+        row: usize,
+        col: usize,
+    },
+}
+
+/// A player of a game
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Player {
+    /// The nickname, without spaces
+    pub nick: String,
+    /// The full name
+    pub name: String,
+}
+
+/// A move of a game record
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct GameMove {
+    /// The index of the player, 0 or 1
+    pub player: usize,
+    /// The tiles on the rack before the move, with `*` for a blank
+    pub rack: Vec<String>,
+    /// The word that is played, including the tiles on the board, or `None` for a pass, an exchange,
+    /// the withdrawal of a phony word, or the tiles that are left at the end of the game
+    pub word: Option<Word>,
+    /// True if the move withdraws the word of the move before it, a phony word that was challenged
+    pub withdrawal: bool,
+    /// The score of the move
+    pub score: i32,
+    /// The total score of the player after the move
+    pub total: i32,
+}
+
+/// A game record, that is written and read in the GCG format
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Game {
+    /// The language of the game, used for the multi-letter tiles and the letter values
+    pub language: Language,
+    /// The two players
+    pub players: [Player; 2],
+    /// The moves, in the order they are played
+    pub moves: Vec<GameMove>,
+}
+
+impl Game {
+    /// Create a game record from consecutive screenshots of one game.
+    ///
+    /// Each screenshot with new tiles is a move, scored with [diff](OcrResults::diff). Screenshots without new tiles
+    /// are skipped. The rack of the screenshots is the rack of player 0, the owner of the phone: a move with tiles
+    /// from the rack of the screenshot before it is a move of player 0, other moves are of player 1. The rack of
+    /// player 1 is unknown, so the rack of the moves of player 1 is the tiles they played.
+    ///
+    /// # Errors
+    /// * A screenshot has removed or changed tiles, or its new tiles are not a legal move, see [Inconsistency].
+    pub fn from_screenshots(
+        screenshots: &[OcrResults],
+        language: Language,
+    ) -> Result<Game, GcgError> {
+        let mut game = Game {
+            language,
+            players: [
+                Player {
+                    nick: String::from("me"),
+                    name: String::from("Me"),
+                },
+                Player {
+                    nick: String::from("opponent"),
+                    name: String::from("Opponent"),
+                },
+            ],
+            moves: Vec::new(),
+        };
+        let mut totals = [0; 2];
+        for (i, pair) in screenshots.windows(2).enumerate() {
+            let (before, after) = (&pair[0], &pair[1]);
+            let diff = before.diff(after, language);
+            if diff.tiles.is_empty() && diff.inconsistencies.is_empty() {
+                continue;
+            }
+            let score = match diff.score {
+                Some(score) if diff.inconsistencies.is_empty() => score,
+                _ => {
+                    return Err(GcgError::Inconsistent {
+                        screenshot: i + 1,
+                        inconsistencies: diff.inconsistencies,
+                    })
+                }
+            };
+            let played: Vec<String> = diff
+                .tiles
+                .iter()
+                .map(|t| {
+                    if t.wildcard {
+                        String::from("*")
+                    } else {
+                        t.tile.clone()
+                    }
+                })
+                .collect();
+            let rack: Vec<String> = before
+                .rack_ocr
+                .first()
+                .map(|rack| {
+                    rack.iter()
+                        .filter(|t| *t != ".")
+                        .map(|t| t.to_lowercase())
+                        .collect()
+                })
+                .unwrap_or_default();
+            let (player, rack) = if is_subset(&played, &rack) {
+                (0, rack)
+            } else {
+                (1, played)
+            };
+            totals[player] += score.total as i32;
+            game.moves.push(GameMove {
+                player,
+                rack,
+                word: score.words.into_iter().next().map(|(word, _)| word),
+                withdrawal: false,
+                score: score.total as i32,
+                total: totals[player],
+            });
+        }
+        Ok(game)
+    }
+
+    /// Write the game in the GCG format.
+    ///
+    /// Letters are upper case and wildcards lower case, blanks on the rack are `?`. The tiles on the board
+    /// that a word plays through are included in the word.
+    ///
+    /// # Errors
+    /// * A word starts outside the board.
+    pub fn to_gcg(&self) -> Result<String, GcgError> {
+        let mut gcg = String::from("#character-encoding UTF-8\n");
+        for (i, player) in self.players.iter().enumerate() {
+            let _ = writeln!(gcg, "#player{} {} {}", i + 1, player.nick, player.name);
+        }
+        for (index, m) in self.moves.iter().enumerate() {
+            let rack: String = m
+                .rack
+                .iter()
+                .map(|t| {
+                    if t == "*" {
+                        String::from("?")
+                    } else {
+                        t.to_uppercase()
+                    }
+                })
+                .collect();
+            let _ = write!(gcg, ">{}: {} ", self.players[m.player].nick, rack);
+            match &m.word {
+                Some(word) => {
+                    let text: String = word
+                        .tiles
+                        .iter()
+                        .enumerate()
+                        .map(|(i, t)| {
+                            if word.is_wildcard(i) {
+                                t.clone()
+                            } else {
+                                t.to_uppercase()
+                            }
+                        })
+                        .collect();
+                    let position = position(word).ok_or(GcgError::Conflict {
+                        index,
+                        row: word.row,
+                        col: word.col,
+                    })?;
+                    let _ = write!(gcg, "{} {} ", position, text);
+                }
+                None if m.withdrawal => gcg.push_str("-- "),
+                None => gcg.push_str("- "),
+            }
+            let _ = writeln!(gcg, "{:+} {}", m.score, m.total);
+        }
+        Ok(gcg)
+    }
+
+    /// Read a game in the GCG format, with the tiles of `language`.
+    ///
+    /// The `#player1` and `#player2` pragmas and the move lines are read, other lines are skipped.
+    /// A `.` in a word is a tile on the board that the word plays through. Moves without a position, like a pass,
+    /// an exchange or the tiles that are left at the end of the game, have no word.
+    ///
+    /// # Errors
+    /// * A move line can not be parsed, or has a player that is not in the pragmas.
+    pub fn from_gcg(gcg: &str, language: Language) -> Result<Game, GcgError> {
+        let mut players = [
+            Player {
+                nick: String::from("player1"),
+                name: String::from("Player 1"),
+            },
+            Player {
+                nick: String::from("player2"),
+                name: String::from("Player 2"),
+            },
+        ];
+        let mut moves = Vec::new();
+        for (n, line) in gcg.lines().enumerate() {
+            let error = |message: &str| GcgError::Parse {
+                line: n + 1,
+                message: message.to_string(),
+            };
+            let line = line.trim();
+            if let Some(pragma) = line.strip_prefix("#player") {
+                let mut fields = pragma.splitn(3, ' ');
+                let index = match fields.next() {
+                    Some("1") => 0,
+                    Some("2") => 1,
+                    _ => return Err(error("Invalid player pragma")),
+                };
+                let nick = fields.next().ok_or_else(|| error("Missing nickname"))?;
+                players[index] = Player {
+                    nick: nick.to_string(),
+                    name: fields.next().unwrap_or(nick).trim().to_string(),
+                };
+            } else if let Some(play) = line.strip_prefix('>') {
+                let (nick, play) = play.split_once(':').ok_or_else(|| error("Missing ':'"))?;
+                let player = players
+                    .iter()
+                    .position(|p| p.nick == nick)
+                    .ok_or_else(|| error("Unknown player"))?;
+                let fields: Vec<&str> = play.split_whitespace().collect();
+                if fields.len() < 3 {
+                    return Err(error("Missing fields"));
+                }
+                let number = |field: &str| field.parse::<i32>().map_err(|_| error("Invalid score"));
+                let total = number(fields[fields.len() - 1])?;
+                let score = number(fields[fields.len() - 2])?;
+                let rack = if fields[0].starts_with('(') {
+                    ""
+                } else {
+                    fields[0]
+                };
+                let word = match fields.len() {
+                    5 => Some(
+                        parse_word(fields[1], fields[2], language)
+                            .ok_or_else(|| error("Invalid position"))?,
+                    ),
+                    _ => None,
+                };
+                let rack = Word::parse(
+                    0,
+                    0,
+                    Direction::Horizontal,
+                    &rack.replace('?', "*"),
+                    language,
+                )
+                .tiles;
+                moves.push(GameMove {
+                    player,
+                    rack,
+                    word,
+                    withdrawal: fields.len() == 4 && fields[1] == "--",
+                    score,
+                    total,
+                });
+            }
+        }
+        Ok(Game {
+            language,
+            players,
+            moves,
+        })
+    }
+
+    /// Replay the moves on an empty board, and return the tiles on the board after each move,
+    /// like [tiles_ocr](crate::OcrResults::tiles_ocr). Moves without a word do not change the board, except
+    /// a withdrawal, that removes the tiles of the move before it.
+    ///
+    /// # Errors
+    /// * A move does not fit on the board, or places a tile on a tile with another letter.
+    pub fn boards(&self) -> Result<Vec<Ocr>, GcgError> {
+        let mut board = Ocr(vec![vec![String::from("."); 15]; 15]);
+        let mut boards = Vec::new();
+        // the tiles that are placed by the previous move
+        let mut placed: Vec<(usize, usize)> = Vec::new();
+        for (index, m) in self.moves.iter().enumerate() {
+            if m.withdrawal {
+                for &(row, col) in &placed {
+                    board[row][col] = String::from(".");
+                }
+            }
+            placed.clear();
+            if let Some(word) = &m.word {
+                for (i, (row, col)) in word.cells().enumerate() {
+                    let conflict = GcgError::Conflict { index, row, col };
+                    let cell = board
+                        .get_mut(row)
+                        .and_then(|tiles| tiles.get_mut(col))
+                        .ok_or_else(|| conflict.clone())?;
+                    // a tile that the word plays through
+                    if word.tiles[i] == "." {
+                        if cell == "." {
+                            return Err(conflict);
+                        }
+                        continue;
+                    }
+                    let tile = if word.is_wildcard(i) {
+                        word.tiles[i].to_uppercase()
+                    } else {
+                        word.tiles[i].clone()
+                    };
+                    if cell != "." && cell.to_lowercase() != word.tiles[i] {
+                        return Err(conflict);
+                    }
+                    if cell == "." {
+                        *cell = tile;
+                        placed.push((row, col));
+                    }
+                }
+            }
+            boards.push(board.clone());
+        }
+        Ok(boards)
+    }
+}
+
+/// Return true if the tiles in `played` are all on `rack`
+fn is_subset(played: &[String], rack: &[String]) -> bool {
+    let mut rack = rack.to_vec();
+    played
+        .iter()
+        .all(|tile| match rack.iter().position(|t| t == tile) {
+            Some(i) => {
+                rack.swap_remove(i);
+                true
+            }
+            None => false,
+        })
+}
+
+/// The GCG position of a word: the row number first for a horizontal word, e.g. `8H`, the column letter
+/// first for a vertical word, e.g. `H8`. Returns `None` if the word starts outside the board.
+fn position(word: &Word) -> Option<String> {
+    if word.row >= COLUMNS.len() {
+        return None;
+    }
+    let col = COLUMNS.get(word.col..word.col + 1)?;
+    Some(match word.direction {
+        Direction::Horizontal => format!("{}{}", word.row + 1, col),
+        Direction::Vertical => format!("{}{}", col, word.row + 1),
+    })
+}
+
+/// Parse a GCG position and word. Upper case letters are tiles, lower case letters are wildcards, and a `.`
+/// is a tile on the board. The tiles of the word are in lower case, with `.` for a tile on the board.
+fn parse_word(position: &str, text: &str, language: Language) -> Option<Word> {
+    let first = position.chars().next()?;
+    let (direction, col, row) = if first.is_ascii_digit() {
+        let split = position.find(|c: char| !c.is_ascii_digit())?;
+        (
+            Direction::Horizontal,
+            &position[split..],
+            &position[..split],
+        )
+    } else {
+        (Direction::Vertical, position.get(..1)?, &position[1..])
+    };
+    let mut chars = col.chars();
+    let col = match (chars.next(), chars.next()) {
+        (Some(ch), None) => COLUMNS.chars().position(|c| c == ch)?,
+        _ => return None,
+    };
+    let row = row.parse::<usize>().ok()?.checked_sub(1)?;
+    // swap the case, wildcards are upper case in a word
+    let text: String = text
+        .chars()
+        .map(|c| {
+            if c.is_uppercase() {
+                c.to_lowercase().to_string()
+            } else {
+                c.to_uppercase().to_string()
+            }
+        })
+        .collect();
+    Some(Word::parse(row, col, direction, &text, language))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::results;
+
+    fn screenshots() -> Vec<OcrResults> {
+        let bonus = [(8, 8, "2w")];
+        let mut rows = vec!["..............."; 7];
+        let first = results(&rows, "qatbde.", &bonus);
+        rows.push("......qat......");
+        let second = results(&rows, "bdexyz*", &bonus);
+        let third = results(&rows, "bdexyz*", &bonus);
+        rows.push("........Ex.....");
+        let fourth = results(&rows, "bdyz...", &bonus);
+        vec![first, second, third, fourth]
+    }
+
+    const GCG: &str = "#character-encoding UTF-8
+#player1 me Me
+#player2 opponent Opponent
+>me: QATBDE 8G QAT +12 12
+>me: BDEXYZ? 9I eX +18 30
+";
+
+    #[test]
+    fn test_to_gcg() -> Result<(), GcgError> {
+        let game = Game::from_screenshots(&screenshots(), Language::English)?;
+        assert_eq!(game.moves.len(), 2);
+        assert_eq!(game.to_gcg()?, GCG);
+
+        // the tiles are not on the rack, a move of the opponent
+        let mut screenshots = screenshots();
+        screenshots[0].rack_ocr = Ocr(vec![vec![String::from("."); 7]]);
+        let game = Game::from_screenshots(&screenshots, Language::English)?;
+        assert_eq!(game.moves[0].player, 1);
+        assert_eq!(game.moves[0].rack, ["q", "a", "t"]);
+        assert_eq!(game.moves[1].total, 18);
+
+        // a word that starts outside the board
+        let mut off_board = game.clone();
+        off_board.moves[1].word.as_mut().unwrap().col = 15;
+        assert_eq!(
+            off_board.to_gcg(),
+            Err(GcgError::Conflict {
+                index: 1,
+                row: 8,
+                col: 15
+            })
+        );
+        let mut off_board = game.clone();
+        off_board.moves[1].word.as_mut().unwrap().row = 15;
+        assert_eq!(
+            off_board.to_gcg(),
+            Err(GcgError::Conflict {
+                index: 1,
+                row: 15,
+                col: 8
+            })
+        );
+
+        // a tile that changed
+        screenshots[2].tiles_ocr[7][7] = String::from("o");
+        let err = Game::from_screenshots(&screenshots, Language::English).unwrap_err();
+        assert!(matches!(err, GcgError::Inconsistent { screenshot: 2, .. }));
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_gcg() -> Result<(), GcgError> {
+        let game = Game::from_gcg(GCG, Language::English)?;
+        assert_eq!(
+            game,
+            Game::from_screenshots(&screenshots(), Language::English)?
+        );
+        let boards = game.boards()?;
+        let screenshots = screenshots();
+        assert_eq!(boards[0].0, screenshots[1].tiles_ocr.0);
+        assert_eq!(boards[1].0, screenshots[3].tiles_ocr.0);
+
+        // a vertical word through a tile on the board, a pass and the tiles left at the end
+        let gcg = "#player1 joe Joe Bloggs
+#player2 ann Ann
+>joe: QAT 8G QAT +24 24
+>ann: CHURR?O H7 CH.rrO +10 10
+>joe: AB - +0 24
+>ann: (AB) +8 18
+";
+        let game = Game::from_gcg(gcg, Language::Spanish)?;
+        assert_eq!(game.players[0].name, "Joe Bloggs");
+        assert_eq!(game.moves[1].rack, ["ch", "u", "rr", "*", "o"]);
+        let word = game.moves[1].word.as_ref().unwrap();
+        assert_eq!(
+            (word.row, word.col, word.direction),
+            (6, 7, Direction::Vertical)
+        );
+        assert_eq!(word.to_string(), "ch.RRo");
+        assert!(game.moves[2].word.is_none());
+        assert_eq!((game.moves[3].score, game.moves[3].total), (8, 18));
+        let boards = game.boards()?;
+        let column: String = (6..10).map(|row| boards[3][row][7].clone()).collect();
+        assert_eq!(column, "chaRRo");
+
+        let err = Game::from_gcg(">bob: A 8H A +1 1", Language::English).unwrap_err();
+        assert_eq!(err.to_string(), "Line 1: Unknown player");
+        // one letter per column
+        let err = Game::from_gcg(">player1: QAT 8GH QAT +12 12", Language::English).unwrap_err();
+        assert_eq!(err.to_string(), "Line 1: Invalid position");
+        let game = Game::from_gcg(
+            ">player1: QAT 8A QAT +12 12\n>player2: QAT 8B QAT +12 12",
+            Language::English,
+        )?;
+        assert!(matches!(
+            game.boards(),
+            Err(GcgError::Conflict {
+                index: 1,
+                row: 7,
+                col: 1
+            })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_withdrawal() -> Result<(), GcgError> {
+        let gcg = "#player1 joe Joe
+#player2 ann Ann
+>joe: QAT 8G QAT +24 24
+>ann: XYZ H7 X.YZ +20 20
+>ann: XYZ -- -20 0
+>joe: BD H7 B.D +6 30
+";
+        let game = Game::from_gcg(gcg, Language::English)?;
+        assert!(game.moves[2].withdrawal && game.moves[2].word.is_none());
+        assert!(!game.moves[1].withdrawal);
+        assert_eq!(
+            game.to_gcg()?,
+            format!("#character-encoding UTF-8\n{}", gcg)
+        );
+        let boards = game.boards()?;
+        let column: String = (6..10).map(|row| boards[1][row][7].clone()).collect();
+        assert_eq!(column, "xayz");
+        // the phony word is removed, the tile it played through stays
+        let column: String = (6..10).map(|row| boards[2][row][7].clone()).collect();
+        assert_eq!(column, ".a..");
+        // the cells of the phony word are free again
+        let column: String = (6..10).map(|row| boards[3][row][7].clone()).collect();
+        assert_eq!(column, "bad.");
+        Ok(())
+    }
+}
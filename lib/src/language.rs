@@ -0,0 +1,238 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A tile in the bag of a [Language]: the letter(s), the number of tiles, and the points of each tile
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tile {
+    /// The letter(s) on the tile, in lower case, e.g. `a` or `ch`
+    pub letter: &'static str,
+    /// The number of tiles with this letter in the bag
+    pub count: u32,
+    /// The points of the tile
+    pub points: u32,
+}
+
+const fn tile(letter: &'static str, count: u32, points: u32) -> Tile {
+    Tile {
+        letter,
+        count,
+        points,
+    }
+}
+
+const ENGLISH: &[Tile] = &[
+    tile("a", 10, 1),
+    tile("b", 2, 4),
+    tile("c", 2, 4),
+    tile("d", 5, 2),
+    tile("e", 12, 1),
+    tile("f", 2, 4),
+    tile("g", 3, 3),
+    tile("h", 3, 4),
+    tile("i", 9, 1),
+    tile("j", 1, 10),
+    tile("k", 1, 5),
+    tile("l", 4, 1),
+    tile("m", 2, 3),
+    tile("n", 6, 1),
+    tile("o", 7, 1),
+    tile("p", 2, 4),
+    tile("q", 1, 10),
+    tile("r", 6, 1),
+    tile("s", 5, 1),
+    tile("t", 7, 1),
+    tile("u", 4, 2),
+    tile("v", 2, 4),
+    tile("w", 2, 4),
+    tile("x", 1, 8),
+    tile("y", 2, 4),
+    tile("z", 1, 10),
+];
+
+const DUTCH: &[Tile] = &[
+    tile("a", 7, 1),
+    tile("b", 2, 4),
+    tile("c", 2, 5),
+    tile("d", 5, 2),
+    tile("e", 18, 1),
+    tile("f", 2, 4),
+    tile("g", 3, 3),
+    tile("h", 2, 4),
+    tile("i", 4, 2),
+    tile("j", 2, 4),
+    tile("k", 3, 3),
+    tile("l", 3, 3),
+    tile("m", 3, 3),
+    tile("n", 11, 1),
+    tile("o", 6, 1),
+    tile("p", 2, 4),
+    tile("q", 1, 10),
+    tile("r", 5, 2),
+    tile("s", 5, 2),
+    tile("t", 5, 2),
+    tile("u", 3, 2),
+    tile("v", 2, 4),
+    tile("w", 2, 5),
+    tile("x", 1, 8),
+    tile("y", 1, 8),
+    tile("z", 2, 5),
+];
+
+const SWEDISH: &[Tile] = &[
+    tile("a", 9, 1),
+    tile("b", 2, 3),
+    tile("c", 1, 8),
+    tile("d", 5, 1),
+    tile("e", 8, 1),
+    tile("f", 2, 3),
+    tile("g", 3, 2),
+    tile("h", 2, 3),
+    tile("i", 5, 1),
+    tile("j", 1, 7),
+    tile("k", 3, 3),
+    tile("l", 5, 2),
+    tile("m", 3, 3),
+    tile("n", 6, 1),
+    tile("o", 6, 2),
+    tile("p", 2, 4),
+    tile("r", 8, 1),
+    tile("s", 8, 1),
+    tile("t", 9, 1),
+    tile("u", 3, 4),
+    tile("v", 2, 3),
+    tile("x", 1, 8),
+    tile("y", 1, 7),
+    tile("z", 1, 8),
+    tile("å", 2, 4),
+    tile("ä", 2, 4),
+    tile("ö", 2, 4),
+];
+
+const SPANISH: &[Tile] = &[
+    tile("a", 12, 1),
+    tile("b", 2, 3),
+    tile("c", 4, 3),
+    tile("ch", 1, 5),
+    tile("d", 5, 2),
+    tile("e", 12, 1),
+    tile("f", 1, 4),
+    tile("g", 2, 2),
+    tile("h", 2, 4),
+    tile("i", 6, 1),
+    tile("j", 1, 8),
+    tile("l", 4, 1),
+    tile("ll", 1, 8),
+    tile("m", 2, 3),
+    tile("n", 5, 1),
+    tile("ñ", 1, 8),
+    tile("o", 9, 1),
+    tile("p", 2, 3),
+    tile("q", 1, 5),
+    tile("r", 5, 1),
+    tile("rr", 1, 8),
+    tile("s", 6, 1),
+    tile("t", 4, 1),
+    tile("u", 5, 1),
+    tile("v", 1, 4),
+    tile("x", 1, 8),
+    tile("y", 1, 4),
+    tile("z", 1, 10),
+];
+
+/// The language of a Wordfeud game, which determines the tiles in the bag
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Language {
+    English,
+    Dutch,
+    Swedish,
+    Spanish,
+}
+
+impl Language {
+    /// All supported languages
+    pub const ALL: [Language; 4] = [
+        Language::English,
+        Language::Dutch,
+        Language::Swedish,
+        Language::Spanish,
+    ];
+
+    /// The letter tiles in the bag, without the blank tiles
+    pub fn tiles(self) -> &'static [Tile] {
+        match self {
+            Language::English => ENGLISH,
+            Language::Dutch => DUTCH,
+            Language::Swedish => SWEDISH,
+            Language::Spanish => SPANISH,
+        }
+    }
+
+    /// The number of blank tiles in the bag
+    pub fn blanks(self) -> u32 {
+        2
+    }
+
+    /// The total number of tiles in the bag, including the blank tiles
+    pub fn total(self) -> u32 {
+        self.tiles().iter().map(|t| t.count).sum::<u32>() + self.blanks()
+    }
+
+    /// Find the tile for `letter`. Upper case letters (wildcards) are found too.
+    pub fn tile(self, letter: &str) -> Option<&'static Tile> {
+        let letter = letter.to_lowercase();
+        self.tiles().iter().find(|t| t.letter == letter)
+    }
+
+    /// The language code, e.g. `en`
+    pub fn code(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::Dutch => "nl",
+            Language::Swedish => "sv",
+            Language::Spanish => "es",
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    /// Parse a language code (`en`) or name (`english`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_lowercase();
+        Language::ALL
+            .iter()
+            .copied()
+            .find(|lang| lang.code() == s || format!("{:?}", lang).to_lowercase() == s)
+            .ok_or_else(|| format!("Unknown language: {}", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language() {
+        assert_eq!(Language::English.total(), 104);
+        assert_eq!(Language::Dutch.total(), 104);
+        assert_eq!(Language::Swedish.total(), 104);
+        assert_eq!(Language::Spanish.total(), 100);
+        assert_eq!(Language::English.tile("Q").map(|t| t.points), Some(10));
+        assert_eq!(Language::Spanish.tile("ch").map(|t| t.count), Some(1));
+        assert!(Language::English.tile("ä").is_none());
+        assert_eq!("nl".parse(), Ok(Language::Dutch));
+        assert_eq!("Swedish".parse(), Ok(Language::Swedish));
+        assert!("klingon".parse::<Language>().is_err());
+    }
+}
@@ -1,22 +1,32 @@
 use crate::error::Error;
-use image::{math::Rect, GrayImage, ImageBuffer, Luma};
-use imageproc::integral_image::{integral_image, integral_squared_image, sum_image_pixels};
+use image::{math::Rect, GenericImageView, ImageBuffer, Luma};
+use imageproc::integral_image::sum_image_pixels;
 use log::debug;
+#[cfg(feature = "serde")]
+use serde::Serialize;
 
 pub const THRESHOLD: f64 = 0.65;
 
 type IntegralImage = ImageBuffer<Luma<u64>, Vec<u64>>;
 
 /// Represents the recognized layout of a wordfeud board.
+///
+/// With the `serde` feature the layout can be serialized. The integral images are skipped.
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct 
 Layout {
+    #[cfg_attr(feature = "serde", serde(skip))]
     integral: IntegralImage,
+    #[cfg_attr(feature = "serde", serde(skip))]
     integral_squared: IntegralImage,
     /// The screen area (the entire screenshot)
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_rect::RectDef"))]
     pub screen: Rect,
     /// The board area (a 15 x 15 grid)
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_rect::RectDef"))]
     pub board_area: Rect,
     /// The rack area (1 row x 7 column grid)
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_rect::RectDef"))]
     pub rack_area: Rect,
     /// The start and end `y` coordinate of the board rows
     pub rows: Vec<(usize, usize)>,
@@ -28,7 +38,9 @@ Layout {
     pub rack_cols: Vec<(usize, usize)>,
 }
 
-#[derive(Debug, PartialEq)]
+/// The state of the segmentation. A [LayoutFailed](crate::Error::LayoutFailed) error holds the state where it failed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum Segment {
     LookForTopBorder(usize),
     InTopBorder,
@@ -41,6 +53,23 @@ pub enum Segment {
     Done,
 }
 
+impl Segment {
+    /// The name of the state, without the row or tile count.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Segment::LookForTopBorder(_) => "LookForTopBorder",
+            Segment::InTopBorder => "InTopBorder",
+            Segment::LookForRisingEdge(_) => "LookForRisingEdge",
+            Segment::InTile(_) => "InTile",
+            Segment::LookForBottomBorder(_) => "LookForBottomBorder",
+            Segment::InBottomBorder => "InBottomBorder",
+            Segment::LookForRack => "LookForRack",
+            Segment::InRack => "InRack",
+            Segment::Done => "Done",
+        }
+    }
+}
+
 fn close(a: u32, b: u32, tol: u32) -> bool {
     (a as i32 - b as i32).abs() <= tol as i32
 }
@@ -54,9 +83,11 @@ impl Layout {
     ///
     /// Only the screen area is set to the image bounding rect. Empty board_area and rack_area.
     /// Empty board and rack rows and columns.
-    pub fn new(img: &GrayImage) -> Layout {
-        let integral: IntegralImage = integral_image::<_, u64>(img);
-        let integral_squared: IntegralImage = integral_squared_image::<_, u64>(img);
+    pub fn new<I>(img: &I) -> Layout
+    where
+        I: GenericImageView<Pixel = Luma<u8>>,
+    {
+        let (integral, integral_squared) = integral_images(img);
         let screen = Rect {
             x: 0,
             y: 0,
@@ -377,6 +408,36 @@ impl Layout {
     }
 }
 
+/// Compute the integral image and the integral squared image of `img` in a single pass.
+///
+/// The results are the same as [imageproc::integral_image::integral_image] and
+/// [imageproc::integral_image::integral_squared_image], but much faster for a full screenshot.
+fn integral_images<I>(img: &I) -> (IntegralImage, IntegralImage)
+where
+    I: GenericImageView<Pixel = Luma<u8>>,
+{
+    let (width, height) = (img.width() as usize, img.height() as usize);
+    let stride = width + 1;
+    let mut integral = vec![0u64; stride * (height + 1)];
+    let mut integral_squared = vec![0u64; stride * (height + 1)];
+    for y in 0..height {
+        let (mut sum, mut sum_squared) = (0u64, 0u64);
+        let (above, current) = (y * stride, (y + 1) * stride);
+        for x in 0..width {
+            let p = img.get_pixel(x as u32, y as u32)[0] as u64;
+            sum += p;
+            sum_squared += p * p;
+            integral[current + x + 1] = integral[above + x + 1] + sum;
+            integral_squared[current + x + 1] = integral_squared[above + x + 1] + sum_squared;
+        }
+    }
+    let (w, h) = (stride as u32, height as u32 + 1);
+    (
+        ImageBuffer::from_raw(w, h, integral).unwrap(),
+        ImageBuffer::from_raw(w, h, integral_squared).unwrap(),
+    )
+}
+
 /// This is a modified copy of [imageproc::integral_image::variance]()
 pub fn variance(
     integral_image: &IntegralImage,
@@ -396,6 +457,15 @@ pub fn variance(
 #[cfg(test)]
 mod tests {
     use image::{GenericImageView, GrayImage, ImageBuffer};
+    use imageproc::integral_image::{integral_image, integral_squared_image};
+
+    #[test]
+    fn test_integral_images() {
+        let img = GrayImage::from_fn(17, 9, |x, y| image::Luma([((x * 31 + y * 77) % 256) as u8]));
+        let (integral, integral_squared) = super::integral_images(&img);
+        assert_eq!(integral, integral_image::<_, u64>(&img));
+        assert_eq!(integral_squared, integral_squared_image::<_, u64>(&img));
+    }
 
     #[test]
     fn test_subimg() {
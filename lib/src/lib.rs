@@ -40,12 +40,49 @@
 //! ```
 
 
+#[cfg(not(target_arch = "wasm32"))]
+mod batch;
+mod buffer;
+mod correct;
+mod diff;
 mod error;
+mod gcg;
+mod language;
 mod layout;
+mod matcher;
+mod pool;
 mod recognizer;
+mod sampler;
+mod score;
+#[cfg(feature = "solver")]
+mod solver;
+#[cfg(feature = "serde")]
+mod serde_rect;
+#[cfg(test)]
+mod test_util;
 mod utils;
+mod validate;
+mod words;
+#[cfg(feature = "watch")]
+mod watch;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub use batch::{BatchItem, BatchReport, BatchResults, Timings};
+pub use buffer::{PixelFormat, ScreenBuffer};
+pub use correct::{Correction, Corrections, Dictionary};
+pub use diff::{Inconsistency, MoveDiff, PlacedTile};
 pub use error::Error;
-pub use layout::Layout;
+pub use gcg::{Game, GameMove, GcgError, Player};
+pub use language::{Language, Tile};
+pub use layout::{Layout, Segment};
+pub use matcher::Template;
+pub use pool::UnseenTiles;
 pub use recognizer::{Board, Ocr, OcrResults, OcrStat, OcrStats};
-pub use utils::{collage, save_templates};
+pub use score::{MoveError, MoveScore, BINGO_BONUS};
+#[cfg(feature = "solver")]
+pub use solver::{Move, Solver};
+pub use utils::{collage, into_gray, save_templates};
+pub use validate::{Location, Warning};
+pub use words::{Direction, Word};
+#[cfg(feature = "watch")]
+pub use watch::{WatchEvent, Watcher};
@@ -0,0 +1,242 @@
+use image::GrayImage;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::cmp::Ordering;
+
+/// A template image, prepared for matching.
+///
+/// The sum of squared pixel values is computed once, when the template is created.
+#[derive(Debug, Clone)]
+pub struct Template {
+    /// The tag of the template: a letter or a bonus
+    pub tag: String,
+    /// The template image
+    pub image: GrayImage,
+    sum_squares: u64,
+}
+
+impl Template {
+    /// Create a new template with `tag` from `image`.
+    pub fn new(tag: &str, image: GrayImage) -> Template {
+        let sum_squares = image.iter().map(|&p| p as u64 * p as u64).sum();
+        Template {
+            tag: String::from(tag),
+            image,
+            sum_squares,
+        }
+    }
+
+    /// Match the template at every position in `area`.
+    ///
+    /// The score is the sum of squared errors, normalized by the square root of the product of
+    /// the sum of squares of the template and the matched area.
+    /// This is the same as [MatchTemplateMethod::SumOfSquaredErrorsNormalized](imageproc::template_matching::MatchTemplateMethod),
+    /// but exploits the small search window: the area is only a few pixels larger than the template.
+    ///
+    /// Returns the minimum score and the first location where it was found.
+    ///
+    /// # Panics
+    /// If the template is larger than `area`.
+    pub fn match_area(&self, area: &GrayImage) -> (f32, (u32, u32)) {
+        let sums = WindowSums::new(area, self.image.dimensions());
+        self.match_window_sums(area, &sums)
+    }
+
+    fn match_window_sums(&self, area: &GrayImage, sums: &WindowSums) -> (f32, (u32, u32)) {
+        let w = area.width() as usize;
+        let (tw, th) = (self.image.width() as usize, self.image.height() as usize);
+        let (pixels, template) = (area.as_raw(), self.image.as_raw());
+        let mut min_value = f32::INFINITY;
+        let mut min_value_location = (0, 0);
+        for y in 0..sums.ny {
+            for x in 0..sums.nx {
+                // sum of squared errors = sum(p^2) + sum(t^2) - 2 * sum(p * t)
+                let mut cross = 0u64;
+                for dy in 0..th {
+                    let row = &pixels[(y + dy) * w + x..][..tw];
+                    let trow = &template[dy * tw..][..tw];
+                    cross += row
+                        .iter()
+                        .zip(trow)
+                        .map(|(&p, &t)| p as u32 * t as u32)
+                        .sum::<u32>() as u64;
+                }
+                let window = sums.sums[y * sums.nx + x];
+                let sse = window + self.sum_squares - 2 * cross;
+                let norm = ((window as f64) * (self.sum_squares as f64)).sqrt();
+                let score = if norm > 0.0 {
+                    (sse as f64 / norm) as f32
+                } else {
+                    sse as f32
+                };
+                if score < min_value {
+                    min_value = score;
+                    min_value_location = (x as u32, y as u32);
+                }
+            }
+        }
+        (min_value, min_value_location)
+    }
+}
+
+/// The sums of squared pixel values of every template sized window in an area.
+struct WindowSums {
+    size: (u32, u32),
+    nx: usize,
+    ny: usize,
+    sums: Vec<u64>,
+}
+
+impl WindowSums {
+    fn new(area: &GrayImage, size: (u32, u32)) -> WindowSums {
+        let mut sums = WindowSums {
+            size,
+            nx: 0,
+            ny: 0,
+            sums: Vec::new(),
+        };
+        sums.update(area);
+        sums
+    }
+
+    /// Compute the sums for `area`, and reuse the buffer.
+    fn update(&mut self, area: &GrayImage) {
+        let (w, h) = (area.width() as usize, area.height() as usize);
+        let (tw, th) = (self.size.0 as usize, self.size.1 as usize);
+        assert!(
+            w >= tw && h >= th,
+            "area must be at least as large as the template"
+        );
+        let (nx, ny) = (w - tw + 1, h - th + 1);
+        let pixels = area.as_raw();
+        self.sums.clear();
+        for y in 0..ny {
+            for x in 0..nx {
+                let sum: u64 = (0..th)
+                    .map(|dy| {
+                        pixels[(y + dy) * w + x..][..tw]
+                            .iter()
+                            .map(|&p| p as u64 * p as u64)
+                            .sum::<u64>()
+                    })
+                    .sum();
+                self.sums.push(sum);
+            }
+        }
+        self.nx = nx;
+        self.ny = ny;
+    }
+}
+
+/// Matches areas with templates, and reuses its buffers for every area.
+///
+/// Create one matcher for each thread, e.g. in the scratch space of the cells.
+#[derive(Default)]
+pub struct Matcher {
+    sums: Vec<WindowSums>,
+    ranked: Vec<(usize, f32, (u32, u32))>,
+}
+
+impl Matcher {
+    /// Match `area` with each of the `templates`, and return the tag, score and location of the best match.
+    ///
+    /// If several templates match equally well, the first one wins.
+    pub fn best_match(
+        &mut self,
+        area: &GrayImage,
+        templates: &[Template],
+    ) -> (String, f32, (u32, u32)) {
+        let (best, min_value, min_value_location) = self.ranked_matches(area, templates)[0];
+        (templates[best].tag.clone(), min_value, min_value_location)
+    }
+
+    /// Match `area` with each of the `templates`, and return the template index, score and location of each match,
+    /// the best match first.
+    ///
+    /// Templates that match equally well keep their order.
+    pub fn ranked_matches(
+        &mut self,
+        area: &GrayImage,
+        templates: &[Template],
+    ) -> &[(usize, f32, (u32, u32))] {
+        // the window sums are shared by all templates with the same size,
+        // the first `n` are the sums of this area
+        let mut n = 0;
+        for template in templates {
+            let size = template.image.dimensions();
+            let i = match self.sums.iter().position(|s| s.size == size) {
+                Some(i) if i < n => continue,
+                Some(i) => {
+                    self.sums[i].update(area);
+                    i
+                }
+                None => {
+                    self.sums.push(WindowSums::new(area, size));
+                    self.sums.len() - 1
+                }
+            };
+            self.sums.swap(n, i);
+            n += 1;
+        }
+        let sums = &self.sums[..n];
+        let match_template = |(i, template): (usize, &Template)| {
+            let size = template.image.dimensions();
+            let sums = sums.iter().find(|s| s.size == size).unwrap();
+            let (value, location) = template.match_window_sums(area, sums);
+            (i, value, location)
+        };
+        #[cfg(feature = "rayon")]
+        templates
+            .par_iter()
+            .enumerate()
+            .map(match_template)
+            .collect_into_vec(&mut self.ranked);
+        #[cfg(not(feature = "rayon"))]
+        {
+            self.ranked.clear();
+            self.ranked
+                .extend(templates.iter().enumerate().map(match_template));
+        }
+        // a stable sort, so the first of equal matches wins
+        self.ranked
+            .sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+        &self.ranked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use imageproc::template_matching::{find_extremes, match_template, MatchTemplateMethod};
+
+    #[test]
+    fn test_match_area() {
+        let area = GrayImage::from_fn(12, 10, |x, y| {
+            image::Luma([((x * 37 + y * 91) % 256) as u8])
+        });
+        let image =
+            GrayImage::from_fn(10, 8, |x, y| image::Luma([((x * 53 + y * 17) % 256) as u8]));
+        let template = Template::new("T", image.clone());
+        let (min_value, min_value_location) = template.match_area(&area);
+        let method = MatchTemplateMethod::SumOfSquaredErrorsNormalized;
+        let expected = find_extremes(&match_template(&area, &image, method));
+        assert!((min_value - expected.min_value).abs() < 1e-5);
+        assert_eq!(min_value_location, expected.min_value_location);
+        let mut matcher = Matcher::default();
+        let (tag, value, location) = matcher.best_match(&area, std::slice::from_ref(&template));
+        assert_eq!(tag, "T");
+        assert_eq!((value, location), (min_value, min_value_location));
+
+        // an exact match ranks first
+        let exact = image::imageops::crop_imm(&area, 1, 2, 10, 8).to_image();
+        let templates = [template, Template::new("E", exact)];
+        let ranked = matcher.ranked_matches(&area, &templates);
+        assert_eq!((ranked[0].0, ranked[0].1, ranked[0].2), (1, 0.0, (1, 2)));
+        assert_eq!((ranked[1].0, ranked[1].1), (0, min_value));
+
+        // the buffers are reused for another area
+        let shifted = GrayImage::from_fn(12, 10, |x, y| *area.get_pixel((x + 1) % 12, y));
+        let ranked = matcher.ranked_matches(&shifted, &templates);
+        assert_eq!((ranked[0].0, ranked[0].1, ranked[0].2), (1, 0.0, (0, 2)));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{Language, OcrResults};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The tiles that are not on the board or the rack: the tiles on the rack of the opponent, and in the bag
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct UnseenTiles {
+    /// The language of the game
+    pub language: Language,
+    /// The number of unseen tiles of each letter. Letters without unseen tiles are included with a count of 0.
+    pub letters: BTreeMap<String, u32>,
+    /// The number of unseen blank tiles
+    pub blanks: u32,
+}
+
+impl UnseenTiles {
+    /// The number of unseen tiles with `letter`
+    pub fn count(&self, letter: &str) -> u32 {
+        self.letters
+            .get(&letter.to_lowercase())
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// The total number of unseen tiles, including blank tiles
+    pub fn total(&self) -> u32 {
+        self.letters.values().sum::<u32>() + self.blanks
+    }
+
+    /// The number of tiles in the bag, if the opponent has a full rack of 7 tiles
+    pub fn in_bag(&self) -> u32 {
+        self.total().saturating_sub(7)
+    }
+}
+
+/// Show the unseen tiles in the order of the alphabet, e.g. `aabcc**`
+impl fmt::Display for UnseenTiles {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for tile in self.language.tiles() {
+            write!(
+                f,
+                "{}",
+                tile.letter.repeat(self.count(tile.letter) as usize)
+            )?;
+        }
+        write!(f, "{}", "*".repeat(self.blanks as usize))
+    }
+}
+
+impl OcrResults {
+    /// Compute the unseen tiles: the tiles of `language` that are not on the board or the rack.
+    ///
+    /// A wildcard on the board uses a blank tile. If there are more tiles of a letter than in the bag, which
+    /// is an OCR error (see [validate_tiles](OcrResults::validate_tiles)), the unseen count of the letter is 0.
+    pub fn unseen_tiles(&self, language: Language) -> UnseenTiles {
+        let counts = self.count_tiles(language);
+        let letters = language
+            .tiles()
+            .iter()
+            .map(|tile| {
+                let seen = counts.letters.get(tile.letter).copied().unwrap_or(0);
+                (tile.letter.to_string(), tile.count.saturating_sub(seen))
+            })
+            .collect();
+        UnseenTiles {
+            language,
+            letters,
+            blanks: language.blanks().saturating_sub(counts.blanks),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unseen_tiles() -> Result<(), crate::Error> {
+        let board = crate::Board::new();
+        // The game is over, the opponent has the last tiles
+        let res = board.recognize_screenshot_from_file("tests/screenshot_swedish.png")?;
+        let unseen = res.unseen_tiles(Language::Swedish);
+        assert_eq!(unseen.to_string(), "c");
+        assert_eq!(unseen.count("C"), 1);
+        assert_eq!((unseen.total(), unseen.in_bag(), unseen.blanks), (1, 0, 0));
+
+        let res = board.recognize_screenshot_from_file("tests/screenshot_english.png")?;
+        let unseen = res.unseen_tiles(Language::English);
+        assert_eq!(unseen.total(), 104 - 51 - 7);
+        assert_eq!(unseen.blanks, 0);
+        assert_eq!(unseen.count("q"), 0);
+        Ok(())
+    }
+}
@@ -1,15 +1,24 @@
 use crate::layout::{Layout, THRESHOLD};
+use crate::matcher::{Matcher, Template};
 use crate::Error;
-use image::imageops::{resize, FilterType};
+use crate::sampler::CellSampler;
+use crate::{into_gray, PixelFormat, ScreenBuffer};
 use image::math::Rect;
-use image::{GenericImageView, GrayImage};
-use imageproc::contrast::threshold;
-use imageproc::template_matching::{find_extremes, match_template, MatchTemplateMethod};
+use image::{GenericImageView, ImageFormat, Luma};
+use log::debug;
+use once_cell::sync::Lazy;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::sync::Arc;
 
 /// Recognized letters or bonus squares, organized as a two-dimensional grid of strings
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ocr(pub Vec<Vec<String>>);
 
 impl Deref for Ocr {
@@ -30,6 +39,8 @@ pub type OcrStats = Vec<OcrStat>;
 
 /// Results for a single template match
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub struct OcrStat {
     /// The linear cell index (0.. nrows * ncols)
     pub index: usize,
@@ -39,9 +50,14 @@ pub struct OcrStat {
     pub min_value: f32,
     /// The location where the best template match was found
     pub min_value_location: (u32, u32),
+    /// The tags and match errors of the next best template matches, best first.
+    /// Only tiles have alternatives, they are used to [correct](crate::OcrResults::correct_words) OCR errors.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub alternatives: Vec<(String, f32)>,
 }
 /// Holds the result of recognize_screenshot: recognized tiles on the board and rack, plus grid with bonus squares.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct OcrResults {
     /// The tiles on the board: a 15x15 grid of tiles.
     ///
@@ -60,8 +76,10 @@ pub struct OcrResults {
     /// Stats for rack recognition
     pub rack_stats: OcrStats,
     /// Board area bounding rectangle
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_rect::RectDef"))]
     pub board_area: Rect,
     /// Rack area bounding rectangle
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_rect::RectDef"))]
     pub rack_area: Rect,
 }
 
@@ -78,7 +96,10 @@ impl fmt::Display for Ocr {
 
 impl OcrResults {}
 
-const START_SQUARE: usize = 15 * 7 + 7;
+pub(crate) const START_SQUARE: usize = 15 * 7 + 7;
+
+/// The number of alternative template matches that are kept for each tile
+const ALTERNATIVES: usize = 3;
 
 /// The templates! macro embeds the templates in the library/
 macro_rules! templates {
@@ -96,19 +117,40 @@ const LETTER_TEMPLATES: &[(&str, &[u8])] = &templates![
 
 const BONUS_TEMPLATES: &[(&str, &[u8])] = &templates!["2L", "3L", "2W", "3W"];
 
-fn template_from_buffer(name: &str, buf: &[u8]) -> (String, GrayImage) {
-    (
-        String::from(name),
-        image::load_from_memory(buf).unwrap().to_luma8(), // can not fail because the templates are embedded
+fn template_from_buffer(name: &str, buf: &[u8]) -> Template {
+    // can not fail because the templates are embedded
+    Template::new(name, image::load_from_memory(buf).unwrap().to_luma8())
+}
+
+fn templates_from_buffers(templates: &[(&str, &[u8])]) -> Arc<Vec<Template>> {
+    Arc::new(
+        templates
+            .iter()
+            .map(|(name, buf)| template_from_buffer(name, buf))
+            .collect(),
     )
 }
 
+/// The embedded templates are decoded only once, and shared by all boards.
+static LETTER_CACHE: Lazy<Arc<Vec<Template>>> =
+    Lazy::new(|| templates_from_buffers(LETTER_TEMPLATES));
+static BONUS_CACHE: Lazy<Arc<Vec<Template>>> =
+    Lazy::new(|| templates_from_buffers(BONUS_TEMPLATES));
+
 /// Wordfeud board recognizer.
+///
+/// A `Board` is cheap to clone, and can be shared between threads.
+#[derive(Debug, Clone)]
 pub struct Board {
     /// The template images used to recognize the tiles
-    pub templates: Vec<(String, GrayImage)>,
+    pub templates: Arc<Vec<Template>>,
     /// The bonus templates used to recognize the bonus cells on the board.
-    pub bonus_templates: Vec<(String, GrayImage)>,
+    pub bonus_templates: Arc<Vec<Template>>,
+    /// Apply a median filter to the tiles before they are matched.
+    ///
+    /// This makes the recognition robust to the compression artefacts in JPEG or WebP screenshots.
+    /// It is enabled automatically when a lossy screenshot is recognized from a file or from memory.
+    pub denoise: bool,
 }
 
 impl Default for Board {
@@ -120,25 +162,20 @@ impl Default for Board {
 impl Board {
     /// Create a new board.
     ///
-    /// The template images are included in the executable.
+    /// The template images are included in the executable. They are decoded when the first board is
+    /// created, and stored in the form that is used for template matching.
     pub fn new() -> Board {
-        let templates = LETTER_TEMPLATES
-            .iter()
-            .map(|(name, buf)| template_from_buffer(name, buf))
-            .collect();
-        let bonus_templates = BONUS_TEMPLATES
-            .iter()
-            .map(|(name, buf)| template_from_buffer(name, buf))
-            .collect();
         Board {
-            templates,
-            bonus_templates,
+            templates: Arc::clone(&LETTER_CACHE),
+            bonus_templates: Arc::clone(&BONUS_CACHE),
+            denoise: false,
         }
     }
 
     /// Recognize a wordfeud board screenshot.
     ///
     /// The `screenshot` must be a grayscale image from the Wordfeud game, at the original resolution. 
+    /// Any grayscale image view can be used, e.g. a [ScreenBuffer](crate::ScreenBuffer) on a borrowed pixel buffer.
     ///
     /// Returns an result that contains the detected tiles on the board and in the rack, and the detected board with
     /// the bonus tiles locations.
@@ -149,28 +186,29 @@ impl Board {
     /// # Errors
     /// * The screenshot can not be segmented properly. 
     ///
-    pub fn recognize_screenshot(&self, screenshot: &GrayImage) -> Result<OcrResults, Error> {
-        let layout = Layout::new(&screenshot).segment()?;
-
-        let cells = Layout::get_cells(&layout.rows, &layout.cols);
-        // println!("{:?}", cells);
-        let tile_index = layout.get_tile_index(&cells);
-        let (tiles_ocr, tiles_stats) = self.recognize_tiles(
-            screenshot,
-            &layout,
-            &tile_index,
-            &cells,
-            &self.templates,
-            (15, 15),
-        );
+    pub fn recognize_screenshot<I>(&self, screenshot: &I) -> Result<OcrResults, Error>
+    where
+        I: GenericImageView<Pixel = Luma<u8>> + Sync,
+    {
+        let (res, _) = self.recognize_with_layout(screenshot)?;
+        Ok(res)
+    }
 
-        let (grid_ocr, grid_stats) =
-            self.recognize_board(screenshot, &layout, &cells, &self.bonus_templates, (15, 15));
+    /// Recognize a wordfeud board screenshot, and also return its segmented [Layout](crate::Layout).
+    ///
+    /// The layout gives the geometry of each cell, e.g. to analyze the recognition quality.
+    /// # Errors
+    /// * The screenshot can not be segmented properly.
+    ///
+    pub fn recognize_with_layout<I>(&self, screenshot: &I) -> Result<(OcrResults, Layout), Error>
+    where
+        I: GenericImageView<Pixel = Luma<u8>> + Sync,
+    {
+        let layout = Layout::new(screenshot).segment()?;
 
-        let cells = Layout::get_cells(&layout.rack_rows, &layout.rack_cols);
-        let index: Vec<usize> = (0..cells.len()).into_iter().collect();
-        let (rack_ocr, rack_stats) =
-            self.recognize_tiles(screenshot, &layout, &index, &cells, &self.templates, (1, 7));
+        let (tiles_ocr, tiles_stats) = self.recognize_tiles(screenshot, &layout);
+        let (grid_ocr, grid_stats) = self.recognize_board(screenshot, &layout);
+        let (rack_ocr, rack_stats) = self.recognize_rack(screenshot, &layout);
 
         let res = OcrResults {
             tiles_ocr,
@@ -183,11 +221,57 @@ impl Board {
             rack_area: layout.rack_area,
         };
 
-        Ok(res)
+        Ok((res, layout))
+    }
+
+    /// Recognize the tiles on the board.
+    ///
+    /// The `layout` must be the segmented layout of `screenshot`.
+    /// Returns a 15x15 grid of tiles, see [OcrResults::tiles_ocr](crate::OcrResults::tiles_ocr).
+    pub fn recognize_tiles<I>(&self, screenshot: &I, layout: &Layout) -> (Ocr, OcrStats)
+    where
+        I: GenericImageView<Pixel = Luma<u8>> + Sync,
+    {
+        let cells = Layout::get_cells(&layout.rows, &layout.cols);
+        let tile_index = layout.get_tile_index(&cells);
+        self.match_tiles(
+            screenshot,
+            layout,
+            &tile_index,
+            &cells,
+            &self.templates,
+            (15, 15),
+        )
+    }
+
+    /// Recognize the bonus squares on the board.
+    ///
+    /// The `layout` must be the segmented layout of `screenshot`.
+    /// Returns a 15x15 grid with bonus squares, see [OcrResults::grid_ocr](crate::OcrResults::grid_ocr).
+    pub fn recognize_board<I>(&self, screenshot: &I, layout: &Layout) -> (Ocr, OcrStats)
+    where
+        I: GenericImageView<Pixel = Luma<u8>> + Sync,
+    {
+        let cells = Layout::get_cells(&layout.rows, &layout.cols);
+        self.match_bonus(screenshot, layout, &cells, &self.bonus_templates, (15, 15))
+    }
+
+    /// Recognize the tiles in the rack.
+    ///
+    /// The `layout` must be the segmented layout of `screenshot`.
+    pub fn recognize_rack<I>(&self, screenshot: &I, layout: &Layout) -> (Ocr, OcrStats)
+    where
+        I: GenericImageView<Pixel = Luma<u8>> + Sync,
+    {
+        let cells = Layout::get_cells(&layout.rack_rows, &layout.rack_cols);
+        let index: Vec<usize> = (0..cells.len()).collect();
+        self.match_tiles(screenshot, layout, &index, &cells, &self.templates, (1, 7))
     }
 
     /// Recognize a Wordfeud board screenshot from an image file path.
     ///
+    /// PNG screenshots are always supported. JPEG and WebP screenshots require the `jpeg` and `webp` features.
+    /// Lossy screenshots are recognized with [denoise](Board::denoise) enabled.
     /// # Errors
     /// * The screenshot file can not be opened. See [image::open](image::open).
     /// * The screenshot can not be segmented properly
@@ -196,18 +280,61 @@ impl Board {
         &self,
         screenshot_filename: &str,
     ) -> Result<OcrResults, Error> {
-        let gray = image::open(&screenshot_filename)?.into_luma8();
-        self.recognize_screenshot(&gray)
+        self.recognize_path(Path::new(screenshot_filename))
+    }
+
+    /// Recognize a Wordfeud board screenshot from an image file path, like
+    /// [recognize_screenshot_from_file](Board::recognize_screenshot_from_file).
+    pub fn recognize_path(&self, path: &Path) -> Result<OcrResults, Error> {
+        let reader = image::io::Reader::open(path)
+            .and_then(|reader| reader.with_guessed_format())
+            .map_err(image::ImageError::from)?;
+        let format = reader.format();
+        let gray = into_gray(reader.decode()?, format);
+        self.for_format(format).recognize_screenshot(&gray)
     }
 
     /// Recognize a Wordfeud board screenshot from an image file loaded in a memory buffer.
+    ///
+    /// PNG screenshots are always supported. JPEG and WebP screenshots require the `jpeg` and `webp` features.
+    /// Lossy screenshots are recognized with [denoise](Board::denoise) enabled.
     /// # Errors
     /// * The screenshot can not be loaded from memory. See [image::load_from_memory](image::load_from_memory).
     /// * The screenshot can not be segmented properly
     ///
     pub fn recognize_screenshot_from_memory(&self, screenshot: &[u8]) -> Result<OcrResults, Error> {
-        let gray = image::load_from_memory(&screenshot)?.into_luma8();
-        self.recognize_screenshot(&gray)
+        let format = image::guess_format(screenshot).ok();
+        let gray = into_gray(image::load_from_memory(screenshot)?, format);
+        self.for_format(format).recognize_screenshot(&gray)
+    }
+
+    /// Return a board that is suitable for screenshots in `format`: lossy images are denoised.
+    pub fn for_format(&self, format: Option<ImageFormat>) -> Board {
+        let lossy = matches!(format, Some(ImageFormat::Jpeg) | Some(ImageFormat::WebP));
+        Board {
+            denoise: self.denoise || lossy,
+            ..self.clone()
+        }
+    }
+
+    /// Recognize a Wordfeud board screenshot in a borrowed pixel buffer.
+    ///
+    /// The buffer contains `height` rows of `width` pixels in `format`, with `stride` bytes per row.
+    /// The pixels are not copied.
+    /// # Errors
+    /// * The buffer does not match the dimensions and the pixel format.
+    /// * The screenshot can not be segmented properly
+    ///
+    pub fn recognize_buffer(
+        &self,
+        pixels: &[u8],
+        width: u32,
+        height: u32,
+        stride: usize,
+        format: PixelFormat,
+    ) -> Result<OcrResults, Error> {
+        let screenshot = ScreenBuffer::new(pixels, width, height, stride, format)?;
+        self.recognize_screenshot(&screenshot)
     }
 
     fn topright(cell: Rect) -> Rect {
@@ -219,30 +346,44 @@ impl Board {
         }
     }
 
-    fn recognize_tiles(
+    fn match_tiles<I>(
         &self,
-        img: &GrayImage,
+        img: &I,
         layout: &Layout,
         tile_index: &[usize],
         cells: &[Rect],
-        templates: &[(String, GrayImage)],
+        templates: &[Template],
         size: (usize, usize),
-    ) -> (Ocr, OcrStats) {
+    ) -> (Ocr, OcrStats)
+    where
+        I: GenericImageView<Pixel = Luma<u8>> + Sync,
+    {
         // create rows x cols empty grid
         let (rows, cols) = size;
-        let row: Vec<String> = (0..cols).into_iter().map(|_| String::from(".")).collect();
-        let mut ocr = Ocr((0..rows)
-            .into_iter()
-            .map(|_| row.clone())
-            .collect::<Vec<_>>());
+        let row: Vec<String> = (0..cols).map(|_| String::from(".")).collect();
+        let mut ocr = Ocr(vec![row; rows]);
         if tile_index.is_empty() {
-            println!("No tiles");
+            debug!("No tiles");
             return (ocr, Vec::new());
         }
 
-        let mut stats = Vec::new();
         let thresh = (THRESHOLD * 256.) as u8;
-        for &index in tile_index.iter() {
+        // Area for template matching. Cell dimension is 68 square
+        // Template dimension is wxh = 38 x 60
+        // convert to binary image improves the template match accurarcy
+        let area = Rect {
+            x: 6,
+            y: 3,
+            width: 40,
+            height: 62,
+        };
+        let scratch = || {
+            (
+                CellSampler::new(68, area, Some(thresh), self.denoise),
+                Matcher::default(),
+            )
+        };
+        let matches = map_cells(tile_index, scratch, |(sampler, matcher), index| {
             let cell = cells[index];
 
             // check if the tile is a blank (in the rack)
@@ -254,21 +395,28 @@ impl Board {
             let (mean, std) = layout.area_stats(&topright);
             let is_wildcard = mean > 0.8 && std < 0.1;
 
-            // create tile image
-            let mut tile: GrayImage = img.view(cell.x, cell.y, cell.width, cell.height).to_image();
-            // convert to binary image improves the template match accurarcy
-            tile = threshold(&tile, thresh);
-            tile = resize(&tile, 68, 68, FilterType::Lanczos3);
-
-            // Area for template matching. Cell dimension is 68 square
-            // Template dimension is wxh = 38 x 60
-            let area = tile.view(6, 3, 40, 62).to_image();
-
             // match templates
-            let (letter, min_value, min_value_location) = if !is_blank {
-                Board::match_template(&area, templates)
+            let stat = if !is_blank {
+                let ranked = matcher.ranked_matches(sampler.sample(img, cell), templates);
+                let (best, min_value, min_value_location) = ranked[0];
+                let alternatives = ranked[1..]
+                    .iter()
+                    .take(ALTERNATIVES)
+                    .map(|&(i, value, _)| (templates[i].tag.clone(), value))
+                    .collect();
+                OcrStat {
+                    index,
+                    tag: templates[best].tag.clone(),
+                    min_value,
+                    min_value_location,
+                    alternatives,
+                }
             } else {
-                (String::from("*"), 0.0_f32, (0_u32, 0_u32))
+                OcrStat {
+                    index,
+                    tag: String::from("*"),
+                    ..OcrStat::default()
+                }
             };
             // use this for debugging
             // if cell.height > 100 {
@@ -276,56 +424,67 @@ impl Board {
             //     area.save(filename).unwrap();
             //     println!("{index} {letter} {min_value:.3}, {min_value_location:?}")
             // }
-            let (row, col) = (index / cols, index % cols);
+            (is_wildcard, stat)
+        });
+
+        let mut stats = Vec::new();
+        for (is_wildcard, stat) in matches {
+            let (row, col) = (stat.index / cols, stat.index % cols);
             ocr[row][col] = if !is_wildcard {
-                letter.to_lowercase()
+                stat.tag.to_lowercase()
             } else {
-                letter.clone()
+                stat.tag.clone()
             };
-            stats.push(OcrStat {
-                index,
-                tag: letter.clone(),
-                min_value,
-                min_value_location,
-            });
+            stats.push(stat);
         }
         (ocr, stats)
     }
 
-    fn recognize_board(
+    fn match_bonus<I>(
         &self,
-        img: &GrayImage,
+        img: &I,
         layout: &Layout,
         cells: &[Rect],
-        templates: &[(String, GrayImage)],
+        templates: &[Template],
         size: (usize, usize),
-    ) -> (Ocr, OcrStats) {
+    ) -> (Ocr, OcrStats)
+    where
+        I: GenericImageView<Pixel = Luma<u8>> + Sync,
+    {
         // create rows x cols empty grid
         let (rows, cols) = size;
 
-        let row: Vec<String> = (0..cols).into_iter().map(|_| String::from("--")).collect();
-        let mut ocr = Ocr((0..rows)
-            .into_iter()
-            .map(|_| row.clone())
-            .collect::<Vec<_>>());
+        let row: Vec<String> = (0..cols).map(|_| String::from("--")).collect();
+        let mut ocr = Ocr(vec![row; rows]);
         ocr[7][7] = String::from("ss"); // start square
-        let mut stats = Vec::new();
-        for (index, cell) in cells.iter().enumerate() {
-            let mean = layout.mean(&cell);
-            if mean > THRESHOLD || mean < 0.25 || index == START_SQUARE {
-                continue;
-            }
 
-            // create tile image
-            let tile: GrayImage = img.view(cell.x, cell.y, cell.width, cell.height).to_image();
-            let tile = resize(&tile, 67, 67, FilterType::Lanczos3);
+        // select the cells that have a bonus
+        let bonus_index: Vec<usize> = cells
+            .iter()
+            .enumerate()
+            .filter(|&(index, cell)| {
+                let mean = layout.mean(cell);
+                (0.25..=THRESHOLD).contains(&mean) && index != START_SQUARE
+            })
+            .map(|(index, _)| index)
+            .collect();
 
-            // Area for template matching. Cell dimension is wxh = 67 x 67.
-            // Template dimension is wxh = 46x26
-            let area = tile.view(8, 21, 48, 28).to_image();
+        // Area for template matching. Cell dimension is wxh = 67 x 67.
+        // Template dimension is wxh = 46x26
+        let area = Rect {
+            x: 8,
+            y: 21,
+            width: 48,
+            height: 28,
+        };
+        let scratch = || (CellSampler::new(67, area, None, false), Matcher::default());
+        let matches = map_cells(&bonus_index, scratch, |(sampler, matcher), index| {
+            // match templates
+            matcher.best_match(sampler.sample(img, cells[index]), templates)
+        });
 
-            // // match templates
-            let (letter, min_value, min_value_location) = Board::match_template(&area, templates);
+        let mut stats = Vec::new();
+        for (&index, (letter, min_value, min_value_location)) in bonus_index.iter().zip(matches) {
             let (row, col) = (index / cols, index % cols);
             ocr[row][col] = letter.to_lowercase();
             stats.push(OcrStat {
@@ -333,36 +492,48 @@ impl Board {
                 tag: letter.to_lowercase(),
                 min_value,
                 min_value_location,
+                alternatives: Vec::new(),
             });
         }
         (ocr, stats)
     }
+}
 
-    fn match_template(
-        tile: &GrayImage,
-        templates: &[(String, GrayImage)],
-    ) -> (String, f32, (u32, u32)) {
-        let method = MatchTemplateMethod::SumOfSquaredErrorsNormalized;
-        let mut matches = templates
-            .iter()
-            .map(|(letter, template)| {
-                (
-                    letter.clone(),
-                    find_extremes(&match_template(&tile, &template, method)),
-                )
-            })
-            .collect::<Vec<_>>();
-        // find the best match
-        matches.sort_by(|a, b| a.1.min_value.partial_cmp(&b.1.min_value).unwrap());
-        let (letter, extreme) = matches[0].clone();
-        (letter, extreme.min_value, extreme.min_value_location)
-    }
+/// Apply `f` to each cell in `index`, and collect the results in the same order as `index`.
+///
+/// The `init` function creates the scratch space that is passed to `f`. It is called once,
+/// or with the `rayon` feature, once for each batch of cells that is processed in parallel.
+fn map_cells<S, T, F, G>(index: &[usize], init: G, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(&mut S, usize) -> T + Send + Sync,
+    G: Fn() -> S + Send + Sync,
+{
+    #[cfg(feature = "rayon")]
+    let results = index.par_iter().map_init(init, |s, &i| f(s, i)).collect();
+    #[cfg(not(feature = "rayon"))]
+    let results = {
+        let mut s = init();
+        index.iter().map(|&i| f(&mut s, i)).collect()
+    };
+    results
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_board_is_shareable() {
+        fn assert_shareable<T: Send + Sync + Clone>() {}
+        assert_shareable::<Board>();
+
+        let board = Board::new();
+        let other = board.clone();
+        assert!(Arc::ptr_eq(&board.templates, &other.templates));
+        assert!(Arc::ptr_eq(&Board::new().bonus_templates, &other.bonus_templates));
+    }
+
     #[test]
     fn test_topright() {
         let cell = Rect {
@@ -0,0 +1,259 @@
+use image::math::Rect;
+use image::{GenericImageView, GrayImage, Luma};
+use std::f32::consts::PI;
+
+/// Extracts the area for template matching from a cell in the screenshot.
+///
+/// The cell is (optionally) denoised with a 3x3 median filter and thresholded, resized to a square of `size` pixels with a Lanczos3 filter,
+/// and cropped to `area`. The result is identical to
+/// ```text
+/// let tile = img.view(cell.x, cell.y, cell.width, cell.height).to_image();
+/// let tile = threshold(&tile, thresh);
+/// let tile = resize(&tile, size, size, FilterType::Lanczos3);
+/// let area = tile.view(area.x, area.y, area.width, area.height).to_image();
+/// ```
+/// but only the pixels in `area` are computed, and the buffers are reused for every cell.
+pub struct CellSampler {
+    size: u32,
+    area: Rect,
+    threshold: Option<u8>,
+    denoise: bool,
+    padded: Vec<u8>,
+    denoised: Vec<u8>,
+    cell: Vec<u8>,
+    columns: Vec<u8>,
+    weights: Option<((u32, u32), Weights, Weights)>,
+    out: GrayImage,
+}
+
+/// The filter weights for a range of output pixels.
+struct Weights {
+    /// For each output pixel: the first input pixel, the weights and the sum of the weights.
+    pixels: Vec<(usize, Vec<f32>, f32)>,
+}
+
+impl CellSampler {
+    /// Create a sampler that resizes cells to `size` x `size` pixels and crops `area`.
+    pub fn new(size: u32, area: Rect, threshold: Option<u8>, denoise: bool) -> CellSampler {
+        CellSampler {
+            size,
+            area,
+            threshold,
+            denoise,
+            padded: Vec::new(),
+            denoised: Vec::new(),
+            cell: Vec::new(),
+            columns: Vec::new(),
+            weights: None,
+            out: GrayImage::new(area.width, area.height),
+        }
+    }
+
+    /// Sample `cell` from `img`, and return the area for template matching.
+    ///
+    /// All cells in a screenshot have the same size, so the filter weights are only computed once.
+    pub fn sample<I>(&mut self, img: &I, cell: Rect) -> &GrayImage
+    where
+        I: GenericImageView<Pixel = Luma<u8>>,
+    {
+        let cw = cell.width as usize;
+        let (aw, ah) = (self.area.width as usize, self.area.height as usize);
+        // copy the (denoised and thresholded) cell
+        if self.denoise {
+            self.median(img, cell);
+        }
+        self.cell.clear();
+        for y in 0..cell.height {
+            for x in 0..cell.width {
+                let p = if self.denoise {
+                    self.denoised[y as usize * cw + x as usize]
+                } else {
+                    img.get_pixel(cell.x + x, cell.y + y)[0]
+                };
+                self.cell.push(match self.threshold {
+                    Some(thresh) if p <= thresh => 0,
+                    Some(_) => 255,
+                    None => p,
+                });
+            }
+        }
+        let size = (cell.width, cell.height);
+        if !matches!(self.weights, Some((s, _, _)) if s == size) {
+            let vertical = Weights::lanczos3(cell.height, self.size, self.area.y, self.area.height);
+            let horizontal = Weights::lanczos3(cell.width, self.size, self.area.x, self.area.width);
+            self.weights = Some((size, vertical, horizontal));
+        }
+        let (_, vertical, horizontal) = self.weights.as_ref().unwrap();
+
+        // resample the columns: cw x ch -> cw x ah
+        self.columns.clear();
+        self.columns.resize(cw * ah, 0);
+        for (oy, (left, ws, sum)) in vertical.pixels.iter().enumerate() {
+            for x in 0..cw {
+                let mut t = 0.0;
+                for (i, w) in ws.iter().enumerate() {
+                    t += self.cell[(left + i) * cw + x] as f32 * w;
+                }
+                self.columns[oy * cw + x] = round(t / sum);
+            }
+        }
+        // resample the rows: cw x ah -> aw x ah
+        let out: &mut [u8] = &mut self.out;
+        for (ox, (left, ws, sum)) in horizontal.pixels.iter().enumerate() {
+            for y in 0..ah {
+                let mut t = 0.0;
+                for (i, w) in ws.iter().enumerate() {
+                    t += self.columns[y * cw + left + i] as f32 * w;
+                }
+                out[y * aw + ox] = round(t / sum);
+            }
+        }
+        &self.out
+    }
+
+    /// Apply a 3x3 median filter to `cell`. This removes most of the compression artefacts
+    /// of lossy image formats, but preserves the edges of the letters.
+    fn median<I>(&mut self, img: &I, cell: Rect)
+    where
+        I: GenericImageView<Pixel = Luma<u8>>,
+    {
+        // copy the cell with a border of 1 pixel, clamped to the image bounds
+        let (w, h) = (cell.width as usize + 2, cell.height as usize + 2);
+        let (xmax, ymax) = (img.width() as i64 - 1, img.height() as i64 - 1);
+        self.padded.clear();
+        for y in 0..h as i64 {
+            for x in 0..w as i64 {
+                let px = (cell.x as i64 + x - 1).clamp(0, xmax) as u32;
+                let py = (cell.y as i64 + y - 1).clamp(0, ymax) as u32;
+                self.padded.push(img.get_pixel(px, py)[0]);
+            }
+        }
+        self.denoised.clear();
+        let mut window = [0u8; 9];
+        for y in 1..h - 1 {
+            for x in 1..w - 1 {
+                for (i, v) in window.iter_mut().enumerate() {
+                    *v = self.padded[(y + i / 3 - 1) * w + x + i % 3 - 1];
+                }
+                window.sort_unstable();
+                self.denoised.push(window[4]);
+            }
+        }
+    }
+}
+
+impl Weights {
+    /// Compute the Lanczos3 weights to resize `len` input pixels to `new_len` output pixels,
+    /// for the `count` output pixels starting at `start`.
+    ///
+    /// The computation follows [image::imageops::resize] exactly.
+    fn lanczos3(len: u32, new_len: u32, start: u32, count: u32) -> Weights {
+        let support = 3.0;
+        let ratio = len as f32 / new_len as f32;
+        let sratio = if ratio < 1.0 { 1.0 } else { ratio };
+        let src_support = support * sratio;
+        let pixels = (start..start + count)
+            .map(|out| {
+                let input = (out as f32 + 0.5) * ratio;
+                let left = ((input - src_support).floor() as i64).clamp(0, len as i64 - 1);
+                let right = ((input + src_support).ceil() as i64)
+                    .max(left + 1)
+                    .min(len as i64);
+                let input = input - 0.5;
+                let mut sum = 0.0;
+                let ws = (left..right)
+                    .map(|i| {
+                        let w = lanczos3((i as f32 - input) / sratio);
+                        sum += w;
+                        w
+                    })
+                    .collect();
+                (left as usize, ws, sum)
+            })
+            .collect();
+        Weights { pixels }
+    }
+}
+
+fn sinc(t: f32) -> f32 {
+    let a = t * PI;
+    if t == 0.0 {
+        1.0
+    } else {
+        a.sin() / a
+    }
+}
+
+fn lanczos3(x: f32) -> f32 {
+    if x.abs() < 3.0 {
+        sinc(x) * sinc(x / 3.0)
+    } else {
+        0.0
+    }
+}
+
+fn round(t: f32) -> u8 {
+    t.clamp(0.0, 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::imageops::{resize, FilterType};
+    use imageproc::contrast::threshold;
+
+    #[test]
+    fn test_sample_is_identical_to_resize() {
+        let img = GrayImage::from_fn(200, 150, |x, y| Luma([((x * x + 7 * y) % 256) as u8]));
+        let cell = Rect {
+            x: 31,
+            y: 17,
+            width: 73,
+            height: 73,
+        };
+        let area = Rect {
+            x: 6,
+            y: 3,
+            width: 40,
+            height: 62,
+        };
+        for &thresh in &[None, Some(166)] {
+            let mut sampler = CellSampler::new(68, area, thresh, false);
+            let mut tile = img.view(cell.x, cell.y, cell.width, cell.height).to_image();
+            if let Some(thresh) = thresh {
+                tile = threshold(&tile, thresh);
+            }
+            let tile = resize(&tile, 68, 68, FilterType::Lanczos3);
+            let expected = tile.view(area.x, area.y, area.width, area.height).to_image();
+            assert_eq!(sampler.sample(&img, cell), &expected);
+            // the second sample reuses the weights
+            assert_eq!(sampler.sample(&img, cell), &expected);
+        }
+    }
+
+    #[test]
+    fn test_sample_denoised() {
+        // a cell with a single noisy pixel is the same as a clean cell after denoising
+        let clean = GrayImage::from_fn(80, 80, |x, _y| Luma([if x < 40 { 50 } else { 220 }]));
+        let mut noisy = clean.clone();
+        noisy.put_pixel(20, 30, Luma([255]));
+        noisy.put_pixel(60, 50, Luma([0]));
+        let cell = Rect {
+            x: 4,
+            y: 4,
+            width: 72,
+            height: 72,
+        };
+        let area = Rect {
+            x: 6,
+            y: 3,
+            width: 40,
+            height: 62,
+        };
+        let mut sampler = CellSampler::new(68, area, Some(166), true);
+        let expected = sampler.sample(&clean, cell).clone();
+        assert_eq!(sampler.sample(&noisy, cell), &expected);
+        let mut sampler = CellSampler::new(68, area, Some(166), false);
+        assert_ne!(sampler.sample(&noisy, cell), &expected);
+    }
+}
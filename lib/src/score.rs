@@ -0,0 +1,349 @@
+use crate::recognizer::START_SQUARE;
+use crate::{Direction, Language, OcrResults, Word};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use thiserror::Error;
+
+/// The bonus for a move that uses all 7 tiles of the rack
+pub const BINGO_BONUS: u32 = 40;
+
+/// Errors for a move that can not be played on the board
+#[derive(Debug, Clone, PartialEq, Error)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum MoveError {
+    /// The word has less than 2 tiles
+    #[error("The word is too short")]
+    TooShort,
+    /// The word does not fit on the board
+    #[error("The word does not fit on the board")]
+    OffBoard,
+    /// A tile of the word differs from the tile on the board
+    #[error("The word has '{tile}' at ({row}, {col}), the board has '{board}'")]
+    Conflict {
+        row: usize,
+        col: usize,
+        tile: String,
+        board: String,
+    },
+    /// The word is part of a longer word on the board
+    #[error("The word is part of a longer word on the board")]
+    Incomplete,
+    /// The word does not place a new tile
+    #[error("The word does not place a new tile")]
+    NoNewTiles,
+    /// The word is not connected to the tiles on the board, or does not cover the start square of an empty board
+    #[error("The word is not connected to the tiles on the board")]
+    NotConnected,
+    /// A letter that is not in the alphabet of the language
+    #[error("Unknown letter '{0}'")]
+    UnknownLetter(String),
+}
+
+/// The score of a move
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct MoveScore {
+    /// The words that are formed by the move, with their score: the word of the move first, then the crossing words
+    pub words: Vec<(Word, u32)>,
+    /// The number of tiles that are placed on the board
+    pub tiles: usize,
+    /// True if all 7 tiles of the rack are placed, which gives a [bonus](BINGO_BONUS)
+    pub bingo: bool,
+    /// The total score
+    pub total: u32,
+}
+
+impl Word {
+    /// Create a word at `(row, col)` from `text`, e.g. `hedonIc`. Upper case letters are wildcards.
+    ///
+    /// Multi-letter tiles of `language`, like `ch` in Spanish, are used where they fit.
+    pub fn parse(
+        row: usize,
+        col: usize,
+        direction: Direction,
+        text: &str,
+        language: Language,
+    ) -> Word {
+        let mut multi: Vec<&str> = language
+            .tiles()
+            .iter()
+            .map(|tile| tile.letter)
+            .filter(|letter| letter.chars().count() > 1)
+            .collect();
+        // the longest tile first
+        multi.sort_by_key(|letter| std::cmp::Reverse(letter.len()));
+        let mut word = Word {
+            row,
+            col,
+            direction,
+            tiles: Vec::new(),
+            wildcards: Vec::new(),
+        };
+        let mut rest = text;
+        while let Some(c) = rest.chars().next() {
+            let len = multi
+                .iter()
+                .find(|letter| {
+                    rest.get(..letter.len())
+                        .map_or(false, |s| s.to_lowercase() == **letter)
+                })
+                .map_or(c.len_utf8(), |letter| letter.len());
+            let tile = &rest[..len];
+            let letter = tile.to_lowercase();
+            if letter != tile {
+                word.wildcards.push(word.tiles.len());
+            }
+            word.tiles.push(letter);
+            rest = &rest[len..];
+        }
+        word
+    }
+}
+
+/// The points of a tile, 0 for a wildcard
+fn points(tile: &str, wildcard: bool, language: Language) -> Result<u32, MoveError> {
+    let tile = language
+        .tile(tile)
+        .ok_or_else(|| MoveError::UnknownLetter(tile.to_string()))?;
+    Ok(if wildcard { 0 } else { tile.points })
+}
+
+impl OcrResults {
+    /// Compute the score of playing `word` on the board, with the letter values of `language`.
+    ///
+    /// The word is the complete word of the move, including the tiles that are already on the board.
+    /// The new tiles get the bonus of their square in [grid_ocr](crate::OcrResults::grid_ocr), and each
+    /// crossing word that is formed by a new tile is scored too. Wildcards score 0 points, and placing
+    /// 7 tiles gives a bonus of 40 points. The words are not checked with a dictionary, and the tiles are
+    /// not checked with the rack.
+    ///
+    /// # Errors
+    /// * The word can not be played on the board, see [MoveError].
+    pub fn score_move(&self, word: &Word, language: Language) -> Result<MoveScore, MoveError> {
+        if word.len() < 2 {
+            return Err(MoveError::TooShort);
+        }
+        let (dr, dc) = word.direction.step();
+        let (end_row, end_col) = (
+            word.row + (word.len() - 1) * dr,
+            word.col + (word.len() - 1) * dc,
+        );
+        if end_row >= 15 || end_col >= 15 {
+            return Err(MoveError::OffBoard);
+        }
+        let board = |row: usize, col: usize| {
+            self.tiles_ocr
+                .get(row)
+                .and_then(|tiles| tiles.get(col))
+                .filter(|tile| tile.as_str() != ".")
+        };
+        // a square that is missing in the grid has no bonus
+        let square = |row: usize, col: usize| {
+            self.grid_ocr
+                .get(row)
+                .and_then(|squares| squares.get(col))
+                .map_or((1, 1), |square| bonus(square))
+        };
+        // the cells before and after the word must be empty
+        let before = (word.row.checked_sub(dr), word.col.checked_sub(dc));
+        if let (Some(row), Some(col)) = before {
+            if board(row, col).is_some() {
+                return Err(MoveError::Incomplete);
+            }
+        }
+        if board(end_row + dr, end_col + dc).is_some() {
+            return Err(MoveError::Incomplete);
+        }
+
+        let is_empty = self.tiles_ocr.iter().flatten().all(|tile| tile == ".");
+        let mut connected = false;
+        let mut new_tiles = Vec::new();
+        let (mut sum, mut multiplier) = (0, 1);
+        for (i, (row, col)) in word.cells().enumerate() {
+            let tile = &word.tiles[i];
+            match board(row, col) {
+                Some(on_board) => {
+                    if on_board.to_lowercase() != *tile {
+                        return Err(MoveError::Conflict {
+                            row,
+                            col,
+                            tile: tile.clone(),
+                            board: on_board.clone(),
+                        });
+                    }
+                    connected = true;
+                    sum += points(tile, *on_board != on_board.to_lowercase(), language)?;
+                }
+                None => {
+                    let (letter_bonus, word_bonus) = square(row, col);
+                    sum += letter_bonus * points(tile, word.is_wildcard(i), language)?;
+                    multiplier *= word_bonus;
+                    new_tiles.push(i);
+                    connected |= is_empty && row * 15 + col == START_SQUARE;
+                }
+            }
+        }
+        if new_tiles.is_empty() {
+            return Err(MoveError::NoNewTiles);
+        }
+
+        let mut words = vec![(word.clone(), sum * multiplier)];
+        // the crossing words of the new tiles
+        let cross = match word.direction {
+            Direction::Horizontal => Direction::Vertical,
+            Direction::Vertical => Direction::Horizontal,
+        };
+        let (cr, cc) = cross.step();
+        for i in new_tiles.iter().copied() {
+            let (row, col) = (word.row + i * dr, word.col + i * dc);
+            // find the start of the crossing word
+            let (mut start_row, mut start_col) = (row, col);
+            while start_row >= cr
+                && start_col >= cc
+                && board(start_row - cr, start_col - cc).is_some()
+            {
+                start_row -= cr;
+                start_col -= cc;
+            }
+            let mut cross_word = Word {
+                row: start_row,
+                col: start_col,
+                direction: cross,
+                tiles: Vec::new(),
+                wildcards: Vec::new(),
+            };
+            let (mut sum, mut multiplier) = (0, 1);
+            let (mut r, mut c) = (start_row, start_col);
+            loop {
+                let (tile, wildcard) = if (r, c) == (row, col) {
+                    let (letter_bonus, word_bonus) = square(row, col);
+                    let tile = &word.tiles[i];
+                    let wildcard = word.is_wildcard(i);
+                    sum += letter_bonus * points(tile, wildcard, language)?;
+                    multiplier *= word_bonus;
+                    (tile.clone(), wildcard)
+                } else {
+                    match board(r, c) {
+                        Some(tile) => {
+                            let wildcard = *tile != tile.to_lowercase();
+                            let tile = tile.to_lowercase();
+                            sum += points(&tile, wildcard, language)?;
+                            (tile, wildcard)
+                        }
+                        None => break,
+                    }
+                };
+                if wildcard {
+                    cross_word.wildcards.push(cross_word.tiles.len());
+                }
+                cross_word.tiles.push(tile);
+                r += cr;
+                c += cc;
+            }
+            if cross_word.len() >= 2 {
+                connected = true;
+                words.push((cross_word, sum * multiplier));
+            }
+        }
+        if !connected {
+            return Err(MoveError::NotConnected);
+        }
+
+        let bingo = new_tiles.len() == 7;
+        let total =
+            words.iter().map(|(_, score)| score).sum::<u32>() + if bingo { BINGO_BONUS } else { 0 };
+        Ok(MoveScore {
+            words,
+            tiles: new_tiles.len(),
+            bingo,
+            total,
+        })
+    }
+}
+
+/// The letter and word multiplier of a bonus square
+fn bonus(square: &str) -> (u32, u32) {
+    match square {
+        "2l" => (2, 1),
+        "3l" => (3, 1),
+        "2w" => (1, 2),
+        "3w" => (1, 3),
+        _ => (1, 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::results;
+
+    #[test]
+    fn test_parse() {
+        let word = Word::parse(3, 4, Direction::Vertical, "CHuRRo", Language::Spanish);
+        assert_eq!(word.tiles, ["ch", "u", "rr", "o"]);
+        assert_eq!(word.wildcards, [0, 2]);
+        let word = Word::parse(0, 0, Direction::Horizontal, "hedonIc", Language::English);
+        assert_eq!(word.len(), 7);
+        assert_eq!(word.to_string(), "hedonIc");
+    }
+
+    #[test]
+    fn test_score_move() {
+        let english = Language::English;
+        // the first move, on the start square
+        let res = results(&[], "", &[(7, 9, "2l"), (7, 5, "3w")]);
+        let word = Word::parse(7, 6, Direction::Horizontal, "qaT", english);
+        let score = res.score_move(&word, english).unwrap();
+        // q = 10, a = 1, T = wildcard on 2l
+        assert_eq!(score.total, 11);
+        assert_eq!(score.words.len(), 1);
+        let word = Word::parse(7, 5, Direction::Horizontal, "qat", english);
+        assert_eq!(res.score_move(&word, english).unwrap().total, 36);
+        let word = Word::parse(0, 0, Direction::Horizontal, "qat", english);
+        assert_eq!(res.score_move(&word, english), Err(MoveError::NotConnected));
+
+        // a crossing word
+        let mut rows = vec!["..............."; 7];
+        rows.push("......qat......");
+        let res = results(&rows, "", &[(8, 9, "2w"), (6, 9, "2l")]);
+        let word = Word::parse(6, 9, Direction::Vertical, "ass", english);
+        let score = res.score_move(&word, english).unwrap();
+        // a on 2l, s on 2w
+        assert_eq!(score.words[0].1, (2 + 1 + 1) * 2);
+        assert_eq!(score.words[1].0.text(), "qats");
+        assert_eq!(score.words[1].1, 10 + 1 + 1 + 1);
+        assert_eq!((score.total, score.tiles, score.bingo), (21, 3, false));
+
+        // the word includes a tile on the board
+        let word = Word::parse(6, 7, Direction::Vertical, "bag", english);
+        let score = res.score_move(&word, english).unwrap();
+        assert_eq!((score.total, score.tiles), (4 + 1 + 3, 2));
+        let word = Word::parse(6, 7, Direction::Vertical, "bog", english);
+        assert!(matches!(
+            res.score_move(&word, english),
+            Err(MoveError::Conflict { .. })
+        ));
+        let word = Word::parse(7, 7, Direction::Horizontal, "at", english);
+        assert_eq!(res.score_move(&word, english), Err(MoveError::Incomplete));
+        let word = Word::parse(7, 6, Direction::Horizontal, "qat", english);
+        assert_eq!(res.score_move(&word, english), Err(MoveError::NoNewTiles));
+        let word = Word::parse(7, 13, Direction::Horizontal, "qat", english);
+        assert_eq!(res.score_move(&word, english), Err(MoveError::OffBoard));
+        let word = Word::parse(7, 13, Direction::Horizontal, "a", english);
+        assert_eq!(res.score_move(&word, english), Err(MoveError::TooShort));
+
+        // a bingo below "qat", that forms "qe", "ar" and "ts"
+        let word = Word::parse(8, 2, Direction::Horizontal, "dusters", english);
+        let score = res.score_move(&word, english).unwrap();
+        assert!(score.bingo);
+        assert_eq!(score.words.len(), 4);
+        assert_eq!(score.words[0].1, 2 + 2 + 1 + 1 + 1 + 1 + 1);
+        assert_eq!(score.total, 9 + (10 + 1) + (1 + 1) + (1 + 1) + BINGO_BONUS);
+
+        // the squares of an incomplete grid have no bonus
+        let mut res = results(&[], "", &[(7, 5, "3w")]);
+        res.grid_ocr.0.truncate(7);
+        let word = Word::parse(7, 5, Direction::Horizontal, "qat", english);
+        assert_eq!(res.score_move(&word, english).unwrap().total, 12);
+    }
+}
@@ -0,0 +1,12 @@
+use image::math::Rect;
+use serde::{Deserialize, Serialize};
+
+/// Serde definition for [Rect](image::math::Rect), which does not implement serde itself.
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Rect")]
+pub struct RectDef {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
@@ -0,0 +1,504 @@
+use crate::recognizer::START_SQUARE;
+use crate::{Dictionary, Direction, Language, MoveScore, OcrResults, Word};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// The number of rows and columns of the board
+const SIZE: usize = 15;
+
+/// The tile index of a letter on the board that is not in the alphabet
+const UNKNOWN: u8 = u8::MAX;
+
+/// A tile on the board: the index of the letter in the alphabet, and true for a wildcard
+type Cell = Option<(u8, bool)>;
+
+/// A node of the word graph, with the edges sorted by tile
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+struct Node {
+    edges: Vec<(u8, u32)>,
+    terminal: bool,
+}
+
+/// A directed acyclic word graph: a trie of the words, where equal suffixes share their nodes
+#[derive(Debug, Clone)]
+struct Dawg {
+    nodes: Vec<Node>,
+    root: u32,
+}
+
+impl Dawg {
+    fn new(words: &[Vec<u8>]) -> Dawg {
+        let mut trie = vec![Node::default()];
+        for word in words {
+            let mut node = 0;
+            for &tile in word {
+                node = match trie[node].edges.iter().find(|(t, _)| *t == tile) {
+                    Some(&(_, child)) => child as usize,
+                    None => {
+                        let child = trie.len();
+                        trie.push(Node::default());
+                        trie[node].edges.push((tile, child as u32));
+                        child
+                    }
+                };
+            }
+            trie[node].terminal = true;
+        }
+        let mut nodes = Vec::new();
+        let root = minimize(&trie, 0, &mut nodes, &mut HashMap::new());
+        Dawg { nodes, root }
+    }
+
+    /// The node after `tile`, if a word continues with it
+    fn child(&self, node: u32, tile: u8) -> Option<u32> {
+        let edges = &self.nodes[node as usize].edges;
+        edges
+            .binary_search_by_key(&tile, |&(t, _)| t)
+            .ok()
+            .map(|i| edges[i].1)
+    }
+
+    fn contains(&self, tiles: &[u8]) -> bool {
+        tiles
+            .iter()
+            .try_fold(self.root, |node, &tile| self.child(node, tile))
+            .map_or(false, |node| self.nodes[node as usize].terminal)
+    }
+}
+
+/// Add the subtree of `node` in `trie` to `nodes`, and return its index. Equal subtrees are added once.
+fn minimize(
+    trie: &[Node],
+    node: usize,
+    nodes: &mut Vec<Node>,
+    register: &mut HashMap<Node, u32>,
+) -> u32 {
+    let mut edges: Vec<(u8, u32)> = trie[node]
+        .edges
+        .iter()
+        .map(|&(tile, child)| (tile, minimize(trie, child as usize, nodes, register)))
+        .collect();
+    edges.sort_unstable();
+    let node = Node {
+        edges,
+        terminal: trie[node].terminal,
+    };
+    if let Some(&index) = register.get(&node) {
+        return index;
+    }
+    let index = nodes.len() as u32;
+    nodes.push(node.clone());
+    register.insert(node, index);
+    index
+}
+
+/// A legal move, found by the [Solver]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Move {
+    /// The word of the move, including the tiles that are already on the board
+    pub word: Word,
+    /// The score of the move
+    pub score: MoveScore,
+    /// The tiles that stay on the rack, with `*` for a blank
+    pub leave: Vec<String>,
+}
+
+/// Show the move like `hedonIc (7, 6) horizontal 36`
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let direction = match self.word.direction {
+            Direction::Horizontal => "horizontal",
+            Direction::Vertical => "vertical",
+        };
+        write!(
+            f,
+            "{} ({}, {}) {} {}",
+            self.word, self.word.row, self.word.col, direction, self.score.total
+        )
+    }
+}
+
+/// Find the legal moves on a recognized board, with the words of a dictionary
+#[derive(Debug, Clone)]
+pub struct Solver {
+    language: Language,
+    dawg: Dawg,
+    words: usize,
+}
+
+impl Solver {
+    /// Create a solver for `language` with the words in `dictionary`.
+    ///
+    /// The words are split in the tiles of the language, words with letters that are not in the alphabet
+    /// and words that do not fit on the board are skipped.
+    pub fn new(dictionary: &Dictionary, language: Language) -> Solver {
+        let mut words: Vec<Vec<u8>> = dictionary
+            .words()
+            .filter_map(|word| {
+                let tiles = Word::parse(0, 0, Direction::Horizontal, word, language).tiles;
+                tiles
+                    .iter()
+                    .map(|tile| index(language, tile))
+                    .collect::<Option<Vec<u8>>>()
+            })
+            .filter(|tiles| (2..=SIZE).contains(&tiles.len()))
+            .collect();
+        words.sort_unstable();
+        Solver {
+            language,
+            dawg: Dawg::new(&words),
+            words: words.len(),
+        }
+    }
+
+    /// The language of the solver
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// The number of words that can be played
+    pub fn len(&self) -> usize {
+        self.words
+    }
+
+    /// Return true if the solver has no words
+    pub fn is_empty(&self) -> bool {
+        self.words == 0
+    }
+
+    /// Find the legal moves with the recognized rack, see [moves_for_rack](Solver::moves_for_rack).
+    pub fn moves(&self, results: &OcrResults) -> Vec<Move> {
+        let rack = results.rack_ocr.first().map_or(&[][..], |rack| &rack[..]);
+        self.moves_for_rack(results, rack)
+    }
+
+    /// Find the legal moves on the recognized board with the tiles of `rack`, ranked by score, the best move first.
+    ///
+    /// The rack has a tile per element: a letter, `*` for a blank or `.` for an empty slot. The moves are
+    /// scored with [score_move](OcrResults::score_move), with the bonus squares of
+    /// [grid_ocr](OcrResults::grid_ocr). A tile on the board that is not in the alphabet can not be part of a word.
+    pub fn moves_for_rack(&self, results: &OcrResults, rack: &[String]) -> Vec<Move> {
+        let mut board = [[None; SIZE]; SIZE];
+        for (row, tiles) in results.tiles_ocr.iter().enumerate().take(SIZE) {
+            for (col, tile) in tiles.iter().enumerate().take(SIZE) {
+                if tile != "." {
+                    let wildcard = *tile != tile.to_lowercase();
+                    board[row][col] =
+                        Some((index(self.language, tile).unwrap_or(UNKNOWN), wildcard));
+                }
+            }
+        }
+        let mut counts = vec![0; self.language.tiles().len()];
+        let mut blanks = 0;
+        for tile in rack {
+            if tile == "*" {
+                blanks += 1;
+            } else if let Some(i) = index(self.language, tile) {
+                counts[i as usize] += 1;
+            }
+        }
+
+        let mut search = Search {
+            solver: self,
+            board: &board,
+            empty: board.iter().flatten().all(Option::is_none),
+            direction: Direction::Horizontal,
+            line: 0,
+            cross: Vec::new(),
+            rack: counts,
+            blanks,
+            tiles: Vec::new(),
+            placed: Vec::new(),
+            found: Vec::new(),
+        };
+        for &direction in &[Direction::Horizontal, Direction::Vertical] {
+            search.direction = direction;
+            for line in 0..SIZE {
+                search.line = line;
+                search.cross = (0..SIZE).map(|i| search.cross_check(i)).collect();
+                for start in 0..SIZE {
+                    if start == 0 || search.cell(start - 1).is_none() {
+                        search.extend(start, self.dawg.root, false);
+                    }
+                }
+            }
+        }
+
+        // a move of a single tile can be found in both directions
+        let mut seen = HashSet::new();
+        let mut moves: Vec<Move> = search
+            .found
+            .into_iter()
+            .filter(|(_, placed, _)| seen.insert(placed.clone()))
+            .filter_map(|(word, _, leave)| {
+                let score = results.score_move(&word, self.language).ok()?;
+                Some(Move { word, score, leave })
+            })
+            .collect();
+        moves.sort_by_key(|m| std::cmp::Reverse(m.score.total));
+        moves
+    }
+}
+
+/// The index of `letter` in the alphabet of `language`
+fn index(language: Language, letter: &str) -> Option<u8> {
+    let letter = letter.to_lowercase();
+    language
+        .tiles()
+        .iter()
+        .position(|tile| tile.letter == letter)
+        .map(|i| i as u8)
+}
+
+/// A placed tile: `(row, col, tile, wildcard)`
+type Placement = (usize, usize, u8, bool);
+
+/// The state of the move generation on one line of the board
+struct Search<'a> {
+    solver: &'a Solver,
+    board: &'a [[Cell; SIZE]; SIZE],
+    /// True if there are no tiles on the board
+    empty: bool,
+    direction: Direction,
+    line: usize,
+    /// The tiles that form a crossing word in each cell of the line, or `None` without crossing tiles
+    cross: Vec<Option<Vec<bool>>>,
+    /// The number of tiles on the rack for each letter
+    rack: Vec<u32>,
+    blanks: u32,
+    /// The tiles of the current word
+    tiles: Vec<(u8, bool)>,
+    /// The tiles of the current word that are placed from the rack
+    placed: Vec<Placement>,
+    /// The words, placed tiles and leaves of the moves that were found
+    found: Vec<(Word, Vec<Placement>, Vec<String>)>,
+}
+
+impl<'a> Search<'a> {
+    /// The `(row, col)` of cell `i` of the line
+    fn position(&self, i: usize) -> (usize, usize) {
+        match self.direction {
+            Direction::Horizontal => (self.line, i),
+            Direction::Vertical => (i, self.line),
+        }
+    }
+
+    fn cell(&self, i: usize) -> Cell {
+        let (row, col) = self.position(i);
+        self.board[row][col]
+    }
+
+    /// Find the tiles that can be placed in cell `i`, by the tiles before and after it in the other direction
+    fn cross_check(&self, i: usize) -> Option<Vec<bool>> {
+        let (row, col) = self.position(i);
+        if self.board[row][col].is_some() {
+            return None;
+        }
+        // the other direction
+        let (dr, dc) = self.direction.step();
+        let (dr, dc) = (dc, dr);
+        let tile = |r: usize, c: usize| self.board.get(r).and_then(|tiles| *tiles.get(c)?);
+        let mut before = Vec::new();
+        let (mut r, mut c) = (row, col);
+        while r >= dr && c >= dc {
+            match tile(r - dr, c - dc) {
+                Some((t, _)) => before.push(t),
+                None => break,
+            }
+            r -= dr;
+            c -= dc;
+        }
+        before.reverse();
+        let mut after = Vec::new();
+        let (mut r, mut c) = (row + dr, col + dc);
+        while let Some((t, _)) = tile(r, c) {
+            after.push(t);
+            r += dr;
+            c += dc;
+        }
+        if before.is_empty() && after.is_empty() {
+            return None;
+        }
+        let alphabet = self.solver.language.tiles().len() as u8;
+        let check = (0..alphabet)
+            .map(|t| {
+                let word: Vec<u8> = before
+                    .iter()
+                    .chain(std::iter::once(&t))
+                    .chain(after.iter())
+                    .copied()
+                    .collect();
+                self.solver.dawg.contains(&word)
+            })
+            .collect();
+        Some(check)
+    }
+
+    /// Extend the current word with cell `i`, from `node` in the word graph.
+    ///
+    /// `connected` is true if the word uses a tile on the board, forms a crossing word, or covers the
+    /// start square of an empty board.
+    fn extend(&mut self, i: usize, node: u32, connected: bool) {
+        let dawg = &self.solver.dawg;
+        if connected
+            && !self.placed.is_empty()
+            && self.tiles.len() >= 2
+            && dawg.nodes[node as usize].terminal
+            && (i == SIZE || self.cell(i).is_none())
+        {
+            self.record(i);
+        }
+        if i == SIZE {
+            return;
+        }
+        match self.cell(i) {
+            Some((tile, wildcard)) => {
+                if let Some(child) = dawg.child(node, tile) {
+                    self.tiles.push((tile, wildcard));
+                    self.extend(i + 1, child, true);
+                    self.tiles.pop();
+                }
+            }
+            None => {
+                let (row, col) = self.position(i);
+                let connected = connected
+                    || self.cross[i].is_some()
+                    || (self.empty && row * SIZE + col == START_SQUARE);
+                for &(tile, child) in &dawg.nodes[node as usize].edges {
+                    if !self.cross[i]
+                        .as_ref()
+                        .map_or(true, |check| check[tile as usize])
+                    {
+                        continue;
+                    }
+                    if self.rack[tile as usize] > 0 {
+                        self.rack[tile as usize] -= 1;
+                        self.place(i, tile, false, child, connected);
+                        self.rack[tile as usize] += 1;
+                    }
+                    if self.blanks > 0 {
+                        self.blanks -= 1;
+                        self.place(i, tile, true, child, connected);
+                        self.blanks += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Place a tile from the rack in cell `i`, and extend the word
+    fn place(&mut self, i: usize, tile: u8, wildcard: bool, node: u32, connected: bool) {
+        let (row, col) = self.position(i);
+        self.tiles.push((tile, wildcard));
+        self.placed.push((row, col, tile, wildcard));
+        self.extend(i + 1, node, connected);
+        self.placed.pop();
+        self.tiles.pop();
+    }
+
+    /// Record the current word, that ends before cell `end`
+    fn record(&mut self, end: usize) {
+        let tiles = self.solver.language.tiles();
+        let (row, col) = self.position(end - self.tiles.len());
+        let word = Word {
+            row,
+            col,
+            direction: self.direction,
+            tiles: self
+                .tiles
+                .iter()
+                .map(|&(t, _)| tiles[t as usize].letter.to_string())
+                .collect(),
+            wildcards: (0..self.tiles.len()).filter(|&i| self.tiles[i].1).collect(),
+        };
+        let mut leave: Vec<String> = self
+            .rack
+            .iter()
+            .enumerate()
+            .flat_map(|(t, &count)| {
+                std::iter::repeat(tiles[t].letter.to_string()).take(count as usize)
+            })
+            .collect();
+        leave.extend(std::iter::repeat(String::from("*")).take(self.blanks as usize));
+        let mut placed = self.placed.clone();
+        placed.sort_unstable();
+        self.found.push((word, placed, leave));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::results;
+
+    #[test]
+    fn test_dawg() {
+        let words = vec![vec![0, 1], vec![0, 1, 2], vec![3, 1, 2], vec![3, 1]];
+        let dawg = Dawg::new(&words);
+        for word in &words {
+            assert!(dawg.contains(word));
+        }
+        assert!(!dawg.contains(&[0]));
+        assert!(!dawg.contains(&[0, 1, 2, 3]));
+        // the suffixes after the first letter are shared
+        assert_eq!(dawg.nodes.len(), 4);
+    }
+
+    #[test]
+    fn test_moves() {
+        let dictionary = Dictionary::new(
+            [
+                "qat", "qats", "at", "ta", "tas", "as", "sat", "eat", "eats", "tea", "teas", "x",
+            ]
+            .iter(),
+        );
+        let solver = Solver::new(&dictionary, Language::English);
+        // "x" is too short
+        assert_eq!(solver.len(), 11);
+
+        // the first move covers the start square
+        let res = results(&[], "qat....", &[(7, 5, "3w"), (8, 9, "2l")]);
+        let moves = solver.moves(&res);
+        assert!(!moves.is_empty());
+        assert!(moves
+            .iter()
+            .all(|m| m.word.cells().any(|(r, c)| (r, c) == (7, 7))));
+        // "qat" on the triple word square
+        assert_eq!(moves[0].to_string(), "qat (7, 5) horizontal 36");
+        assert!(moves[0].leave.is_empty());
+
+        let mut rows = vec!["..............."; 7];
+        rows.push("......qat......");
+        let res = results(&rows, "se*....", &[(7, 5, "3w"), (8, 9, "2l")]);
+        let moves = solver.moves(&res);
+        for m in &moves {
+            assert!(dictionary.contains(&m.word.text()), "{}", m);
+            for (word, _) in &m.score.words {
+                assert!(dictionary.contains(&word.text()), "{}", m);
+            }
+        }
+        assert!(moves
+            .windows(2)
+            .all(|w| w[0].score.total >= w[1].score.total));
+        // "qats" with the s on the board, and "as" down with the a of "qat"
+        let qats = moves
+            .iter()
+            .find(|m| m.word.text() == "qats" && m.word.wildcards.is_empty());
+        assert_eq!(qats.unwrap().score.total, 13);
+        assert!(moves
+            .iter()
+            .any(|m| m.word.to_string() == "aS" && m.word.direction == Direction::Vertical));
+        // a single tile move is found once
+        let singles = moves
+            .iter()
+            .filter(|m| m.score.tiles == 1 && m.word.to_string() == "qats")
+            .count();
+        assert_eq!(singles, 1);
+        let best = &moves[0];
+        assert!(best.score.total >= 13);
+        assert_eq!(best.leave.len(), 3 - best.score.tiles);
+    }
+}
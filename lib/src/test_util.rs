@@ -0,0 +1,31 @@
+use crate::{Ocr, OcrResults};
+use image::math::Rect;
+
+/// Create results from the rows of the board and the rack, on a grid with the start square and the
+/// `bonus` squares
+pub(crate) fn results(rows: &[&str], rack: &str, bonus: &[(usize, usize, &str)]) -> OcrResults {
+    let cells = |s: &str| s.chars().map(|c| c.to_string()).collect::<Vec<_>>();
+    let mut tiles: Vec<Vec<String>> = rows.iter().map(|row| cells(row)).collect();
+    tiles.resize(15, cells(&".".repeat(15)));
+    let mut grid = vec![vec![String::from("--"); 15]; 15];
+    grid[7][7] = String::from("ss");
+    for &(row, col, square) in bonus {
+        grid[row][col] = square.to_string();
+    }
+    let area = Rect {
+        x: 0,
+        y: 0,
+        width: 0,
+        height: 0,
+    };
+    OcrResults {
+        tiles_ocr: Ocr(tiles),
+        grid_ocr: Ocr(grid),
+        rack_ocr: Ocr(vec![cells(rack)]),
+        tiles_stats: Vec::new(),
+        grid_stats: Vec::new(),
+        rack_stats: Vec::new(),
+        board_area: area,
+        rack_area: area,
+    }
+}
@@ -1,9 +1,27 @@
 use image::imageops::{resize, FilterType};
 use image::math::Rect;
-use image::{GenericImage, GenericImageView, GrayImage, ImageBuffer, Luma, SubImage};
+use image::{
+    DynamicImage, GenericImage, GenericImageView, GrayImage, ImageBuffer, ImageFormat, Luma,
+    SubImage,
+};
 use imageproc::map::map_pixels;
 use std::path::{Path, PathBuf};
 
+/// Convert a decoded screenshot in `format` to gray.
+///
+/// The WebP decoder of `image` returns the luma plane of a lossy WebP image in the video range 16..235,
+/// which is stretched to the full range of a PNG screenshot.
+pub fn into_gray(img: DynamicImage, format: Option<ImageFormat>) -> GrayImage {
+    match img {
+        DynamicImage::ImageLuma8(gray) if format == Some(ImageFormat::WebP) => {
+            map_pixels(&gray, |_, _, Luma([p])| {
+                Luma([((p.saturating_sub(16) as u32 * 255 + 109) / 219).min(255) as u8])
+            })
+        }
+        img => img.into_luma8(),
+    }
+}
+
 /// Create a collage from parts of a source image.
 /// TODO: All parts must be the same size
 pub fn collage(source: &GrayImage, parts: &[Rect], maxrows: Option<u32>) -> GrayImage {
@@ -0,0 +1,329 @@
+use crate::recognizer::START_SQUARE;
+use crate::{Language, OcrResults};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The location of a tile: a cell on the board, or a place in the rack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Location {
+    Board { row: usize, col: usize },
+    Rack(usize),
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Location::Board { row, col } => write!(f, "board ({}, {})", row, col),
+            Location::Rack(i) => write!(f, "rack {}", i),
+        }
+    }
+}
+
+/// An impossible state of the recognized tiles, which indicates an OCR error
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
+pub enum Warning {
+    /// A letter that is not in the alphabet of the language
+    UnknownLetter { letter: String, location: Location },
+    /// More tiles with a letter than there are in the bag
+    TooManyTiles {
+        letter: String,
+        found: u32,
+        available: u32,
+    },
+    /// More wildcards on the board plus blank tiles on the rack than there are blank tiles in the bag
+    TooManyBlanks { found: u32, available: u32 },
+    /// There are tiles on the board, but not on the start square
+    StartSquareEmpty,
+    /// A group of tiles that is not connected to the tiles on the start square
+    Disconnected { locations: Vec<Location> },
+    /// A tile without neighbours
+    IsolatedTile { location: Location },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Warning::UnknownLetter { letter, location } => {
+                write!(f, "Unknown letter '{}' at {}", letter, location)
+            }
+            Warning::TooManyTiles {
+                letter,
+                found,
+                available,
+            } => write!(
+                f,
+                "Found {} '{}' tiles, there are only {}",
+                found, letter, available
+            ),
+            Warning::TooManyBlanks { found, available } => write!(
+                f,
+                "Found {} blank tiles, there are only {}",
+                found, available
+            ),
+            Warning::StartSquareEmpty => write!(f, "The start square is empty"),
+            Warning::Disconnected { locations } => {
+                write!(f, "{} tiles are not connected:", locations.len())?;
+                for location in locations {
+                    write!(f, " {}", location)?;
+                }
+                Ok(())
+            }
+            Warning::IsolatedTile { location } => write!(f, "Isolated tile at {}", location),
+        }
+    }
+}
+
+/// The number of tiles of each letter, and of blank tiles
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct TileCounts {
+    pub letters: BTreeMap<String, u32>,
+    pub blanks: u32,
+    pub unknown: Vec<(String, Location)>,
+}
+
+/// A cell on the board or the rack
+enum Cell<'a> {
+    Empty,
+    Letter(&'a str),
+    Blank,
+}
+
+fn board_cell(tile: &str) -> Cell<'_> {
+    match tile {
+        "." => Cell::Empty,
+        _ if tile != tile.to_lowercase() => Cell::Blank,
+        _ => Cell::Letter(tile),
+    }
+}
+
+fn rack_cell(tile: &str) -> Cell<'_> {
+    match tile {
+        "." => Cell::Empty,
+        "*" => Cell::Blank,
+        _ => Cell::Letter(tile),
+    }
+}
+
+impl OcrResults {
+    /// Count the tiles on the board and the rack. Letters that are not in the alphabet of `language` are not counted.
+    pub(crate) fn count_tiles(&self, language: Language) -> TileCounts {
+        let board = self.tiles_ocr.iter().enumerate().flat_map(|(row, tiles)| {
+            tiles
+                .iter()
+                .enumerate()
+                .map(move |(col, tile)| (board_cell(tile), Location::Board { row, col }))
+        });
+        let rack = self
+            .rack_ocr
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(i, tile)| (rack_cell(tile), Location::Rack(i)));
+
+        let mut counts = TileCounts::default();
+        for (cell, location) in board.chain(rack) {
+            match cell {
+                Cell::Empty => {}
+                Cell::Blank => counts.blanks += 1,
+                Cell::Letter(letter) => match language.tile(letter) {
+                    Some(tile) => *counts.letters.entry(tile.letter.to_string()).or_default() += 1,
+                    None => counts.unknown.push((letter.to_string(), location)),
+                },
+            }
+        }
+        counts
+    }
+
+    /// Check that the tiles on the board and the rack can exist in a game in `language`.
+    ///
+    /// Returns a warning for each letter that is not in the alphabet, each letter with more tiles than in the bag,
+    /// and for more blank tiles (wildcards on the board and `*` on the rack) than in the bag.
+    /// A wildcard can be any letter, so a wildcard with an unknown letter is not reported.
+    pub fn validate_tiles(&self, language: Language) -> Vec<Warning> {
+        let counts = self.count_tiles(language);
+        let mut warnings: Vec<Warning> = counts
+            .unknown
+            .into_iter()
+            .map(|(letter, location)| Warning::UnknownLetter { letter, location })
+            .collect();
+        for tile in language.tiles() {
+            let found = counts.letters.get(tile.letter).copied().unwrap_or(0);
+            if found > tile.count {
+                warnings.push(Warning::TooManyTiles {
+                    letter: tile.letter.to_string(),
+                    found,
+                    available: tile.count,
+                });
+            }
+        }
+        if counts.blanks > language.blanks() {
+            warnings.push(Warning::TooManyBlanks {
+                found: counts.blanks,
+                available: language.blanks(),
+            });
+        }
+        warnings
+    }
+
+    /// Check that the tiles on the board form a legal position: all tiles are connected, and cover the start square.
+    ///
+    /// Returns a warning if the start square is empty while there are tiles elsewhere, for each group of tiles that
+    /// is not connected to the start square, and for each tile without neighbours. If the start square is empty,
+    /// the largest group of tiles is taken as the connected group.
+    pub fn validate_geometry(&self) -> Vec<Warning> {
+        let occupied: Vec<bool> = self
+            .tiles_ocr
+            .iter()
+            .flat_map(|row| row.iter().map(|tile| tile != "."))
+            .collect();
+        if occupied.len() != 15 * 15 {
+            return Vec::new();
+        }
+        let mut groups = groups(&occupied);
+        let mut warnings = Vec::new();
+        if groups.is_empty() {
+            return warnings;
+        }
+        let main = match groups.iter().position(|g| g.contains(&START_SQUARE)) {
+            Some(main) => main,
+            None => {
+                warnings.push(Warning::StartSquareEmpty);
+                // the first largest group
+                let max = groups.iter().map(Vec::len).max().unwrap_or(0);
+                groups.iter().position(|g| g.len() == max).unwrap_or(0)
+            }
+        };
+        for (i, group) in groups.iter_mut().enumerate() {
+            let location = |&index: &usize| Location::Board {
+                row: index / 15,
+                col: index % 15,
+            };
+            if group.len() == 1 {
+                warnings.push(Warning::IsolatedTile {
+                    location: location(&group[0]),
+                });
+            } else if i != main {
+                group.sort_unstable();
+                warnings.push(Warning::Disconnected {
+                    locations: group.iter().map(location).collect(),
+                });
+            }
+        }
+        warnings
+    }
+}
+
+/// Find the groups of orthogonally connected cells on a 15x15 board, in the order of their first cell
+fn groups(occupied: &[bool]) -> Vec<Vec<usize>> {
+    let mut seen = vec![false; occupied.len()];
+    let mut groups = Vec::new();
+    for start in 0..occupied.len() {
+        if !occupied[start] || seen[start] {
+            continue;
+        }
+        seen[start] = true;
+        let mut group = vec![start];
+        let mut i = 0;
+        while i < group.len() {
+            let index = group[i];
+            let (row, col) = (index / 15, index % 15);
+            let neighbours = [
+                (row > 0).then(|| index - 15),
+                (row < 14).then(|| index + 15),
+                (col > 0).then(|| index - 1),
+                (col < 14).then(|| index + 1),
+            ];
+            for next in neighbours.iter().flatten().copied() {
+                if occupied[next] && !seen[next] {
+                    seen[next] = true;
+                    group.push(next);
+                }
+            }
+            i += 1;
+        }
+        groups.push(group);
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::results;
+
+    #[test]
+    fn test_validate_tiles() {
+        let res = results(
+            &["..qi...........", "...Q...........", ".......ä......."],
+            "q**b..",
+            &[],
+        );
+        let warnings = res.validate_tiles(Language::English);
+        assert_eq!(
+            warnings,
+            vec![
+                Warning::UnknownLetter {
+                    letter: "ä".to_string(),
+                    location: Location::Board { row: 2, col: 7 }
+                },
+                Warning::TooManyTiles {
+                    letter: "q".to_string(),
+                    found: 2,
+                    available: 1
+                },
+                Warning::TooManyBlanks {
+                    found: 3,
+                    available: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_geometry() {
+        let mut rows = vec!["..............."; 15];
+        rows[1] = "..a............";
+        rows[7] = ".....cat.......";
+        rows[8] = ".......o.....xy";
+        rows[9] = ".............z.";
+        let res = results(&rows, "", &[]);
+        let board = |row, col| Location::Board { row, col };
+        assert_eq!(
+            res.validate_geometry(),
+            vec![
+                Warning::IsolatedTile {
+                    location: board(1, 2)
+                },
+                Warning::Disconnected {
+                    locations: vec![board(8, 13), board(8, 14), board(9, 13)]
+                },
+            ]
+        );
+
+        rows[7] = "...............";
+        let res = results(&rows, "", &[]);
+        let warnings = res.validate_geometry();
+        assert_eq!(warnings[0], Warning::StartSquareEmpty);
+        assert_eq!(warnings.len(), 3);
+
+        assert_eq!(results(&[], "", &[]).validate_geometry(), vec![]);
+    }
+
+    #[test]
+    fn test_validate_screenshot() -> Result<(), crate::Error> {
+        let board = crate::Board::new();
+        let res = board.recognize_screenshot_from_file("tests/screenshot_swedish.png")?;
+        assert_eq!(res.validate_tiles(Language::Swedish), vec![]);
+        assert_eq!(res.validate_geometry(), vec![]);
+        let res = board.recognize_screenshot_from_file("tests/screenshot_dutch_3.png")?;
+        assert_eq!(res.validate_tiles(Language::Dutch), vec![]);
+        assert_eq!(res.validate_geometry(), vec![]);
+        Ok(())
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{Board, Error, OcrResults};
+use log::{debug, warn};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// The result of recognizing a new screenshot in a watched directory
+#[derive(Debug)]
+pub struct WatchEvent {
+    /// The path of the screenshot
+    pub path: PathBuf,
+    /// The recognized screenshot, or the reason why it failed
+    pub result: Result<OcrResults, Error>,
+    /// The JSON sidecar that was written next to the screenshot.
+    ///
+    /// `None` if the screenshot could not be loaded, or the sidecar could not be written.
+    /// It is retried when the file changes.
+    pub sidecar: Option<PathBuf>,
+    /// The reason why the sidecar could not be written
+    pub write_error: Option<io::Error>,
+}
+
+/// The size and modification time of a screenshot, when it was last seen
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FileState {
+    len: u64,
+    modified: Option<SystemTime>,
+    processed: bool,
+}
+
+/// Watch a directory for new PNG screenshots, and recognize them.
+///
+/// The directory is polled. A screenshot is recognized when its size and modification time did not change
+/// since the previous poll, so it is fully written. The result is written in a JSON sidecar next to
+/// the screenshot: `screenshot.png` gives `screenshot.json`. Screenshots that already have a sidecar are skipped,
+/// also when the watcher is restarted.
+///
+/// The sidecar holds the serialized [OcrResults](crate::OcrResults), or `{"error": {"reason": .., "message": ..}}`
+/// if the screenshot was not recognized.
+///
+/// # Example
+/// ```no_run
+/// # use wordfeud_ocr::{Board, Watcher};
+/// let mut watcher = Watcher::new(Board::new(), "screenshots");
+/// watcher.run(|event| println!("{}: {}", event.path.display(), event.result.is_ok()))?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Watcher {
+    board: Board,
+    dir: PathBuf,
+    interval: Duration,
+    files: HashMap<PathBuf, FileState>,
+}
+
+impl Watcher {
+    /// Create a watcher that recognizes the screenshots in `dir` with `board`, polling every second.
+    pub fn new<P: AsRef<Path>>(board: Board, dir: P) -> Watcher {
+        Watcher {
+            board,
+            dir: dir.as_ref().to_path_buf(),
+            interval: Duration::from_secs(1),
+            files: HashMap::new(),
+        }
+    }
+
+    /// Set the poll interval.
+    pub fn interval(mut self, interval: Duration) -> Watcher {
+        self.interval = interval;
+        self
+    }
+
+    /// Return the path of the JSON sidecar of `screenshot`.
+    pub fn sidecar_path(screenshot: &Path) -> PathBuf {
+        screenshot.with_extension("json")
+    }
+
+    /// Scan the directory once, and recognize the screenshots that are fully written.
+    ///
+    /// A new screenshot is recognized on the second poll that sees it unchanged. A sidecar that can not be written
+    /// is reported in the [event](WatchEvent::write_error) of its screenshot.
+    /// # Errors
+    /// * The directory can not be read
+    pub fn poll(&mut self) -> io::Result<Vec<WatchEvent>> {
+        let mut paths: Vec<PathBuf> = fs::read_dir(&self.dir)?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| is_png(path) && path.is_file())
+            .collect();
+        paths.sort();
+        // forget the files that were removed
+        let present: HashSet<&PathBuf> = paths.iter().collect();
+        self.files.retain(|path, _| present.contains(path));
+
+        let mut events = Vec::new();
+        for path in paths {
+            let sidecar = Watcher::sidecar_path(&path);
+            if sidecar.exists() {
+                self.files.remove(&path);
+                continue;
+            }
+            let metadata = match fs::metadata(&path) {
+                Ok(metadata) => metadata,
+                Err(_) => continue, // removed during the scan
+            };
+            let state = FileState {
+                len: metadata.len(),
+                modified: metadata.modified().ok(),
+                processed: false,
+            };
+            match self.files.get_mut(&path) {
+                Some(seen) if seen.len == state.len && seen.modified == state.modified => {
+                    if seen.processed {
+                        continue;
+                    }
+                    seen.processed = true;
+                }
+                _ => {
+                    debug!("{}: waiting until it is fully written", path.display());
+                    self.files.insert(path, state);
+                    continue;
+                }
+            }
+            let result = self.board.recognize_path(&path);
+            let (sidecar, write_error) = match &result {
+                // the file may still be incomplete: retry when it changes
+                Err(Error::ImageError(err)) => {
+                    warn!("{}: {}", path.display(), err);
+                    (None, None)
+                }
+                _ => match write_sidecar(&sidecar, &result) {
+                    Ok(()) => (Some(sidecar), None),
+                    Err(err) => {
+                        warn!("{}: {}", sidecar.display(), err);
+                        (None, Some(err))
+                    }
+                },
+            };
+            events.push(WatchEvent {
+                path,
+                result,
+                sidecar,
+                write_error,
+            });
+        }
+        Ok(events)
+    }
+
+    /// Poll the directory until it can not be read, and call `f` for each recognized screenshot.
+    pub fn run<F>(&mut self, mut f: F) -> io::Result<()>
+    where
+        F: FnMut(&WatchEvent),
+    {
+        loop {
+            for event in self.poll()? {
+                f(&event);
+            }
+            thread::sleep(self.interval);
+        }
+    }
+}
+
+fn is_png(path: &Path) -> bool {
+    path.extension()
+        .map_or(false, |ext| ext.eq_ignore_ascii_case("png"))
+}
+
+/// Write the sidecar to a temporary file first, so a reader never sees a partial sidecar.
+fn write_sidecar(sidecar: &Path, result: &Result<OcrResults, Error>) -> io::Result<()> {
+    let value = match result {
+        Ok(res) => serde_json::to_value(res)?,
+        Err(err) => json!({ "error": { "reason": err.reason(), "message": err.message() } }),
+    };
+    let tmp = sidecar.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(&value)?)?;
+    fs::rename(&tmp, sidecar)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watch() -> io::Result<()> {
+        let dir = std::env::temp_dir().join(format!("wordfeud-ocr-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        fs::copy("tests/screenshot_english.png", dir.join("english.png"))?;
+        // the sidecar of this screenshot can not be written
+        fs::copy("tests/screenshot_english.png", dir.join("blocked.png"))?;
+        fs::create_dir_all(dir.join("blocked.json.tmp"))?;
+        fs::copy("../images/logo.png", dir.join("logo.png"))?;
+        fs::write(dir.join("notes.txt"), "not a screenshot")?;
+
+        let mut watcher = Watcher::new(Board::new(), &dir);
+        // first poll: the screenshots are new
+        assert!(watcher.poll()?.is_empty());
+        // second poll: the screenshots are unchanged, so they are recognized
+        let events = watcher.poll()?;
+        assert_eq!(events.len(), 3);
+        assert!(events[0].result.is_ok());
+        assert!(events[0].sidecar.is_none() && events[0].write_error.is_some());
+        assert!(events[1].result.is_ok());
+        assert!(matches!(events[2].result, Err(Error::LayoutFailed(_))));
+        assert!(events[2].sidecar.is_some() && events[2].write_error.is_none());
+        fs::remove_file(dir.join("blocked.png"))?;
+        fs::remove_dir(dir.join("blocked.json.tmp"))?;
+
+        let sidecar: serde_json::Value =
+            serde_json::from_slice(&fs::read(dir.join("english.json"))?)?;
+        assert_eq!(sidecar["tiles_ocr"].as_array().map(Vec::len), Some(15));
+        let sidecar: serde_json::Value = serde_json::from_slice(&fs::read(dir.join("logo.json"))?)?;
+        assert_eq!(sidecar["error"]["reason"], "LayoutFailed(LookForTopBorder)");
+
+        // the screenshots are processed only once, also by a new watcher
+        assert!(watcher.poll()?.is_empty());
+        let mut watcher = Watcher::new(Board::new(), &dir);
+        assert!(watcher.poll()?.is_empty());
+        assert!(watcher.poll()?.is_empty());
+
+        fs::remove_dir_all(&dir)
+    }
+}
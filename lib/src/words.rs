@@ -0,0 +1,193 @@
+use crate::{Ocr, OcrResults};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::fmt;
+
+/// The direction of a word on the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Direction {
+    Horizontal,
+    Vertical,
+}
+
+impl Direction {
+    /// The `(row, col)` step to the next tile
+    pub fn step(self) -> (usize, usize) {
+        match self {
+            Direction::Horizontal => (0, 1),
+            Direction::Vertical => (1, 0),
+        }
+    }
+}
+
+/// A word on the board
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Word {
+    /// The row of the first tile
+    pub row: usize,
+    /// The column of the first tile
+    pub col: usize,
+    /// The direction of the word
+    pub direction: Direction,
+    /// The letters of the tiles, in lower case. A tile can have more than one letter, e.g. `ch`.
+    pub tiles: Vec<String>,
+    /// The positions in `tiles` of the blank tiles that are used as a wildcard
+    pub wildcards: Vec<usize>,
+}
+
+impl Word {
+    /// The word as a lower case string, e.g. `hedonic`
+    pub fn text(&self) -> String {
+        self.tiles.concat()
+    }
+
+    /// The number of tiles in the word
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// A word always has at least 2 tiles
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// Return true if the tile at `position` is a wildcard
+    pub fn is_wildcard(&self, position: usize) -> bool {
+        self.wildcards.contains(&position)
+    }
+
+    /// The `(row, col)` of each tile
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> {
+        let (dr, dc) = self.direction.step();
+        let (row, col) = (self.row, self.col);
+        (0..self.len()).map(move |i| (row + i * dr, col + i * dc))
+    }
+}
+
+/// Show the word like the board: wildcards in upper case, e.g. `hedonIc`
+impl fmt::Display for Word {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, tile) in self.tiles.iter().enumerate() {
+            if self.is_wildcard(i) {
+                write!(f, "{}", tile.to_uppercase())?;
+            } else {
+                write!(f, "{}", tile)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Ocr {
+    /// Extract the words from a grid of tiles, like [tiles_ocr](crate::OcrResults::tiles_ocr).
+    ///
+    /// Returns every horizontal and vertical sequence of 2 or more tiles: first the horizontal words
+    /// row by row, then the vertical words column by column.
+    pub fn words(&self) -> Vec<Word> {
+        let rows = self.len();
+        let cols = self.iter().map(Vec::len).max().unwrap_or(0);
+        let tile = |row: usize, col: usize| {
+            self.get(row)
+                .and_then(|tiles| tiles.get(col))
+                .filter(|tile| tile.as_str() != ".")
+        };
+        let mut words = Vec::new();
+        for (direction, lines, len) in [
+            (Direction::Horizontal, rows, cols),
+            (Direction::Vertical, cols, rows),
+        ]
+        .iter()
+        .copied()
+        {
+            for line in 0..lines {
+                let cell = |i: usize| match direction {
+                    Direction::Horizontal => (line, i),
+                    Direction::Vertical => (i, line),
+                };
+                let mut i = 0;
+                while i < len {
+                    let (row, col) = cell(i);
+                    let mut word = Word {
+                        row,
+                        col,
+                        direction,
+                        tiles: Vec::new(),
+                        wildcards: Vec::new(),
+                    };
+                    while let Some(t) = tile(cell(i).0, cell(i).1) {
+                        let letter = t.to_lowercase();
+                        if letter != *t {
+                            word.wildcards.push(word.tiles.len());
+                        }
+                        word.tiles.push(letter);
+                        i += 1;
+                    }
+                    if word.len() >= 2 {
+                        words.push(word);
+                    }
+                    // skip the empty cell after the word
+                    i += 1;
+                }
+            }
+        }
+        words
+    }
+}
+
+impl OcrResults {
+    /// Extract the words on the board, see [Ocr::words](crate::Ocr::words).
+    pub fn words(&self) -> Vec<Word> {
+        self.tiles_ocr.words()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&[&str]]) -> Ocr {
+        Ocr(rows
+            .iter()
+            .map(|row| row.iter().map(|s| s.to_string()).collect())
+            .collect())
+    }
+
+    #[test]
+    fn test_words() {
+        let ocr = grid(&[
+            &["c", "a", "t", ".", "."],
+            &["h", ".", "o", ".", "q"],
+            &["CH", "e", "RR", "o", "."],
+        ]);
+        let words = ocr.words();
+        let texts: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        assert_eq!(texts, ["cat", "CHeRRo", "chCH", "toRR"]);
+        let word = &words[1];
+        assert_eq!(
+            (word.row, word.col, word.direction),
+            (2, 0, Direction::Horizontal)
+        );
+        assert_eq!(word.tiles, ["ch", "e", "rr", "o"]);
+        assert_eq!(word.wildcards, [0, 2]);
+        assert_eq!(word.text(), "cherro");
+        let word = &words[3];
+        assert_eq!(word.direction, Direction::Vertical);
+        assert_eq!(word.cells().collect::<Vec<_>>(), [(0, 2), (1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_screenshot_words() -> Result<(), crate::Error> {
+        let board = crate::Board::new();
+        let res = board.recognize_screenshot_from_file("tests/screenshot_english.png")?;
+        let words = res.words();
+        let word = words.iter().find(|w| w.text() == "hedonic").unwrap();
+        assert_eq!((word.row, word.col), (7, 6));
+        assert_eq!(word.wildcards, [5]);
+        assert_eq!(word.to_string(), "hedonIc");
+        assert!(words.iter().all(|w| w.len() >= 2));
+        Ok(())
+    }
+}
@@ -1,9 +1,9 @@
 use anyhow::{Context, Result};
 use std::time::Instant;
-use wordfeud_ocr::Board;
+use wordfeud_ocr::{Board, PixelFormat};
 
 fn test_recognize_screenshot(screenshot_path: &str, expect: &str) -> Result<()> {
-    let img = image::open(&screenshot_path)
+    let img = image::open(screenshot_path)
         .with_context(|| format!("Failed to open {}", screenshot_path))?;
     let gray = img.into_luma8();
     let board = Board::new();
@@ -89,4 +89,144 @@ fn test_screenshot_dutch_5() -> Result<()> {
         include_str!("screenshot_dutch_5.expect"),
     )?;
     Ok(())
-}
\ No newline at end of file
+}
+
+/// The parallel recognizer gives exactly the same results as the sequential recognizer,
+/// including the scores of the matches. Recognition in a pool with one thread is sequential.
+#[cfg(feature = "rayon")]
+#[test]
+fn test_recognize_parallel() -> Result<()> {
+    let board = Board::new();
+    let sequential = rayon::ThreadPoolBuilder::new().num_threads(1).build()?;
+    let parallel = rayon::ThreadPoolBuilder::new().num_threads(4).build()?;
+    let mut paths: Vec<_> = std::fs::read_dir("tests")?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<_, _>>()?;
+    paths.retain(|path| path.extension().map_or(false, |ext| ext == "png"));
+    paths.sort();
+    assert!(!paths.is_empty());
+    for path in paths {
+        let gray = image::open(&path)?.into_luma8();
+        let expect = sequential.install(|| board.recognize_screenshot(&gray));
+        let res = parallel.install(|| board.recognize_screenshot(&gray));
+        assert_eq!(
+            format!("{:?}", res),
+            format!("{:?}", expect),
+            "{}",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_recognize_buffer() -> Result<()> {
+    let path = "tests/screenshot_english.png";
+    let board = Board::new();
+    let expect = board.recognize_screenshot_from_file(path)?;
+    let mut bgra = image::open(path)?.into_rgba8();
+    bgra.pixels_mut().for_each(|p| p.0.swap(0, 2));
+    let (width, height) = bgra.dimensions();
+    let res = board.recognize_buffer(
+        bgra.as_raw(),
+        width,
+        height,
+        4 * width as usize,
+        PixelFormat::Bgra8,
+    )?;
+    assert_eq!(res.tiles_ocr.to_string(), expect.tiles_ocr.to_string());
+    assert_eq!(res.rack_ocr.to_string(), expect.rack_ocr.to_string());
+    assert_eq!(res.grid_ocr.to_string(), expect.grid_ocr.to_string());
+    Ok(())
+}
+
+/// Screenshots that are re-encoded as JPEG must give the same results as the original PNG.
+#[cfg(feature = "jpeg")]
+#[test]
+fn test_jpeg_screenshots() -> Result<()> {
+    let screenshots = [
+        ("tests/screenshot_english.png", include_str!("screenshot_english.expect")),
+        ("tests/screenshot_dutch.png", include_str!("screenshot_dutch.expect")),
+        ("tests/screenshot_dutch_1.png", include_str!("screenshot_dutch_1.expect")),
+        ("tests/screenshot_dutch_2.png", include_str!("screenshot_dutch_2.expect")),
+        ("tests/screenshot_dutch_3.png", include_str!("screenshot_dutch_3.expect")),
+        ("tests/screenshot_dutch_4.png", include_str!("screenshot_dutch_4.expect")),
+        ("tests/screenshot_dutch_5.png", include_str!("screenshot_dutch_5.expect")),
+        ("tests/screenshot_swedish.png", include_str!("screenshot_swedish.expect")),
+    ];
+    let board = Board::new();
+    for &(screenshot_path, expect) in screenshots.iter() {
+        let img = image::open(screenshot_path)?;
+        for &quality in [85, 40].iter() {
+            let mut jpeg = Vec::new();
+            image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, quality)
+                .encode_image(&img)?;
+            let res = board.recognize_screenshot_from_memory(&jpeg)?;
+            let ocr = format!(
+                "Screenshot: {}\n\nTiles:\n{}\n\nLetters: {}\n\nGrid:\n{}\n",
+                screenshot_path, res.tiles_ocr, res.rack_ocr, res.grid_ocr
+            );
+            assert_eq!(ocr, expect, "quality {}", quality);
+        }
+    }
+    Ok(())
+}
+
+/// A screenshot that is re-encoded as lossy WebP must give the same results as the original PNG.
+#[cfg(feature = "webp")]
+#[test]
+fn test_webp_screenshot() -> Result<()> {
+    let board = Board::new();
+    let res = board.recognize_screenshot_from_file("tests/screenshot_english.webp")?;
+    let ocr = format!(
+        "Screenshot: {}\n\nTiles:\n{}\n\nLetters: {}\n\nGrid:\n{}\n",
+        "tests/screenshot_english.png", res.tiles_ocr, res.rack_ocr, res.grid_ocr
+    );
+    assert_eq!(ocr, include_str!("screenshot_english.expect"));
+    Ok(())
+}
+
+#[test]
+fn test_recognize_batch() {
+    let board = Board::new();
+    let paths = [
+        "tests/screenshot_english.png",
+        "tests/missing.png",
+        "../images/logo.png",
+        "tests/screenshot_swedish.png",
+    ];
+    let batch = board.recognize_batch(&paths);
+    assert_eq!(batch.items.len(), 4);
+    assert!(batch.items[0].result.is_ok());
+    assert!(batch.items[3].result.is_ok());
+    let report = &batch.report;
+    assert_eq!((report.total, report.succeeded), (4, 2));
+    assert_eq!(report.success_rate(), 0.5);
+    assert_eq!(report.failures.values().sum::<usize>(), 2);
+    assert_eq!(report.failures["ImageError"], 1);
+    assert_eq!(report.failures["LayoutFailed(LookForTopBorder)"], 1);
+    let text = report.to_string();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(
+        lines[..3],
+        [
+            "Recognized 2 of 4 screenshots (50.0%)",
+            "  ImageError: 1",
+            "  LayoutFailed(LookForTopBorder): 1"
+        ]
+    );
+    assert!(lines[3].starts_with("Time per screenshot: min "));
+    assert_eq!(lines.len(), 4);
+}
+
+#[test]
+fn test_recognize_with_layout() -> Result<()> {
+    let gray = image::open("tests/screenshot_english.png")?.into_luma8();
+    let board = Board::new();
+    let (res, layout) = board.recognize_with_layout(&gray)?;
+    assert_eq!(res.board_area, layout.board_area);
+    assert_eq!((layout.rows.len(), layout.cols.len()), (15, 15));
+    let expect = board.recognize_screenshot(&gray)?;
+    assert_eq!(res.tiles_ocr.to_string(), expect.tiles_ocr.to_string());
+    Ok(())
+}
@@ -0,0 +1,134 @@
+"""Type stubs for the pywordfeud_ocr extension module."""
+
+from typing import Any, Dict, List, Optional, Tuple
+
+import numpy as np
+
+Rect = Tuple[int, int, int, int]
+"""A rectangle `(x, y, width, height)`"""
+
+class WordfeudOcrException(Exception):
+    """A screenshot can not be recognized. The subclass tells the reason."""
+
+    reason: str
+    """The reason without details, e.g. `LayoutFailed(LookForRack)`"""
+
+class BoardNotSquareException(WordfeudOcrException):
+    """The detected board is not square"""
+
+    aspect_ratio: float
+
+class LayoutFailedException(WordfeudOcrException):
+    """The board could not be segmented"""
+
+    segment: str
+    """The segmentation state where it failed, e.g. `LookForTopBorder`"""
+    count: Optional[int]
+    """The row or tile count of the state, if it has one"""
+
+class InvalidBufferException(WordfeudOcrException):
+    """The screenshot buffer does not match its dimensions and pixel format"""
+
+    details: str
+
+class ImageException(WordfeudOcrException):
+    """The screenshot could not be opened or decoded"""
+
+    kind: str
+    """`Decoding`, `Encoding`, `Parameter`, `Limits`, `Unsupported` or `IoError`"""
+    image_error: str
+    """The message of the underlying image error"""
+
+class OcrStat:
+    """Results for a single template match"""
+
+    @property
+    def index(self) -> int: ...
+    @property
+    def row(self) -> int: ...
+    @property
+    def col(self) -> int: ...
+    @property
+    def tag(self) -> str: ...
+    @property
+    def min_value(self) -> float: ...
+    @property
+    def min_value_location(self) -> Tuple[int, int]: ...
+    @property
+    def cell(self) -> Rect: ...
+    @property
+    def alternatives(self) -> List[Tuple[str, float]]: ...
+
+class Layout:
+    """The layout of a Wordfeud screenshot: the board and rack area, and the rows and columns of their cells"""
+
+    @property
+    def screen(self) -> Rect: ...
+    @property
+    def board_area(self) -> Rect: ...
+    @property
+    def rack_area(self) -> Rect: ...
+    @property
+    def rows(self) -> List[Tuple[int, int]]: ...
+    @property
+    def cols(self) -> List[Tuple[int, int]]: ...
+    @property
+    def rack_rows(self) -> List[Tuple[int, int]]: ...
+    @property
+    def rack_cols(self) -> List[Tuple[int, int]]: ...
+    @property
+    def row_stats(self) -> List[Tuple[int, int]]: ...
+    @property
+    def col_stats(self) -> List[Tuple[int, int]]: ...
+    @property
+    def rack_col_stats(self) -> List[Tuple[int, int]]: ...
+    def board_cells(self) -> np.ndarray:
+        """The cells on the board, as a 15×15×4 `uint32` array"""
+    def rack_cells(self) -> np.ndarray:
+        """The cells on the rack, as a 1×7×4 `uint32` array"""
+
+class OcrResults:
+    """The recognized tiles on the board and the rack, and the bonus squares"""
+
+    @property
+    def tiles_ocr(self) -> List[List[str]]: ...
+    @property
+    def grid_ocr(self) -> List[List[str]]: ...
+    @property
+    def rack_ocr(self) -> List[str]: ...
+    @property
+    def tiles_stats(self) -> List[OcrStat]: ...
+    @property
+    def grid_stats(self) -> List[OcrStat]: ...
+    @property
+    def rack_stats(self) -> List[OcrStat]: ...
+    @property
+    def board_area(self) -> Rect: ...
+    @property
+    def rack_area(self) -> Rect: ...
+    @property
+    def layout(self) -> Layout: ...
+    @property
+    def tiles(self) -> List[str]: ...
+    @property
+    def rack(self) -> str: ...
+    def tiles_scores(self) -> np.ndarray:
+        """The match error of each tile on the board, as a 15×15 `float32` array"""
+    def grid_scores(self) -> np.ndarray:
+        """The match error of each bonus square, as a 15×15 `float32` array"""
+    def rack_scores(self) -> np.ndarray:
+        """The match error of each tile on the rack"""
+
+class Board:
+    """Wordfeud board recognizer"""
+
+    def __init__(self) -> None: ...
+    def recognize_screenshot_from_file(self, screenshot_filename: str) -> OcrResults: ...
+    def recognize_screenshot_from_memory(self, screenshot: bytes) -> OcrResults: ...
+    def recognize_array(self, screenshot: np.ndarray, channel_order: str = "rgb") -> OcrResults: ...
+    def layout_from_file(self, screenshot_filename: str) -> Layout: ...
+    def layout_from_memory(self, screenshot: bytes) -> Layout: ...
+    def layout_from_array(self, screenshot: np.ndarray, channel_order: str = "rgb") -> Layout: ...
+
+def recognize_screenshot_from_file(screenshot_filename: str) -> Dict[str, Any]: ...
+def recognize_screenshot_from_memory(screenshot: bytes) -> Dict[str, Any]: ...
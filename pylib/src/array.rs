@@ -0,0 +1,103 @@
+use image::math::Rect;
+use numpy::{PyArray, PyArray1, PyArray2, PyArray3, PyReadonlyArrayDyn};
+use pyo3::{exceptions::PyValueError, prelude::*};
+use wordfeud_ocr::{PixelFormat, ScreenBuffer};
+
+/// A screenshot in a NumPy array: H×W gray, or H×W×3 or H×W×4 color.
+///
+/// The pixels are copied, so the recognition can run without the GIL while Python threads write to the array.
+pub struct Screenshot {
+    pixels: Vec<u8>,
+    width: u32,
+    height: u32,
+    format: PixelFormat,
+}
+
+impl Screenshot {
+    /// `channel_order` is `"rgb"` or `"bgr"` (OpenCV), for color arrays.
+    pub fn new(array: &PyReadonlyArrayDyn<u8>, channel_order: &str) -> PyResult<Self> {
+        let view = array.as_array();
+        let bgr = match channel_order {
+            "rgb" => false,
+            "bgr" => true,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "channel_order must be 'rgb' or 'bgr', not '{}'",
+                    channel_order
+                )))
+            }
+        };
+        let format = match (view.shape(), bgr) {
+            ([_, _], _) => PixelFormat::Gray8,
+            ([_, _, 3], false) => PixelFormat::Rgb8,
+            ([_, _, 3], true) => PixelFormat::Bgr8,
+            ([_, _, 4], false) => PixelFormat::Rgba8,
+            ([_, _, 4], true) => PixelFormat::Bgra8,
+            (shape, _) => {
+                return Err(PyValueError::new_err(format!(
+                    "expected an array with shape (H, W), (H, W, 3) or (H, W, 4), not {:?}",
+                    shape
+                )))
+            }
+        };
+        let pixels = match array.as_slice() {
+            Ok(pixels) => pixels.to_vec(),
+            Err(_) => view.iter().copied().collect(),
+        };
+        Ok(Screenshot {
+            pixels,
+            width: view.shape()[1] as u32,
+            height: view.shape()[0] as u32,
+            format,
+        })
+    }
+
+    /// A view of the screenshot for the recognizer
+    pub fn buffer(&self) -> Result<ScreenBuffer<'_>, wordfeud_ocr::Error> {
+        let stride = self.width as usize * self.format.bytes_per_pixel();
+        ScreenBuffer::new(&self.pixels, self.width, self.height, stride, self.format)
+    }
+}
+
+/// Return the cells as an array with shape `(rows, cols, 4)`: the `x, y, width, height` of each cell.
+pub fn cells_array<'py>(
+    py: Python<'py>,
+    cells: &[Rect],
+    cols: usize,
+) -> PyResult<&'py PyArray3<u32>> {
+    let values: Vec<u32> = cells
+        .iter()
+        .flat_map(|r| vec![r.x, r.y, r.width, r.height])
+        .collect();
+    let rows = cells.len().checked_div(cols).unwrap_or(0);
+    PyArray::from_vec(py, values).reshape([rows, cols, 4])
+}
+
+/// Return the match errors of `stats` in an array of `len` cells. Cells without a match are NaN.
+pub fn scores_vec(stats: &[crate::results::OcrStat], len: usize) -> Vec<f32> {
+    let mut scores = vec![f32::NAN; len];
+    for stat in stats {
+        if let Some(score) = scores.get_mut(stat.index) {
+            *score = stat.min_value;
+        }
+    }
+    scores
+}
+
+/// Return the match errors of `stats` as an array with shape `(rows, cols)`.
+pub fn scores_array2<'py>(
+    py: Python<'py>,
+    stats: &[crate::results::OcrStat],
+    (rows, cols): (usize, usize),
+) -> PyResult<&'py PyArray2<f32>> {
+    PyArray::from_vec(py, scores_vec(stats, rows * cols)).reshape([rows, cols])
+}
+
+/// Return the match errors of `stats` as an array with `len` cells.
+pub fn scores_array1<'py>(
+    py: Python<'py>,
+    stats: &[crate::results::OcrStat],
+    len: usize,
+) -> &'py PyArray1<f32> {
+    PyArray::from_vec(py, scores_vec(stats, len))
+}
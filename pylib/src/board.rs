@@ -0,0 +1,122 @@
+use crate::array::Screenshot;
+use crate::results::{Layout, OcrResults};
+use crate::WordfeudOcrError;
+use numpy::PyReadonlyArrayDyn;
+use pyo3::prelude::*;
+
+/// Wordfeud board recognizer.
+///
+/// The templates are decoded once, and shared by all boards.
+/// The GIL is released during recognition, so several threads can recognize screenshots in parallel.
+#[pyclass]
+#[derive(Debug, Clone, Default)]
+pub struct Board {
+    board: wordfeud_ocr::Board,
+}
+
+/// Open a screenshot file, and return the decoded image with its format.
+fn open(
+    screenshot_filename: &str,
+) -> Result<(image::DynamicImage, Option<image::ImageFormat>), image::ImageError> {
+    let reader = image::io::Reader::open(screenshot_filename)?.with_guessed_format()?;
+    let format = reader.format();
+    Ok((reader.decode()?, format))
+}
+
+/// Decode a screenshot that is loaded in memory, and return it with its format.
+fn load(
+    screenshot: &[u8],
+) -> Result<(image::DynamicImage, Option<image::ImageFormat>), image::ImageError> {
+    let format = image::guess_format(screenshot).ok();
+    Ok((image::load_from_memory(screenshot)?, format))
+}
+
+/// Recognize a decoded screenshot, denoised when its `format` is lossy.
+fn recognize(
+    board: &wordfeud_ocr::Board,
+    (img, format): (image::DynamicImage, Option<image::ImageFormat>),
+) -> Result<OcrResults, WordfeudOcrError> {
+    let (res, layout) = board
+        .for_format(format)
+        .recognize_with_layout(&wordfeud_ocr::into_gray(img, format))?;
+    Ok(OcrResults::new(&res, &layout))
+}
+
+fn layout(
+    (img, format): (image::DynamicImage, Option<image::ImageFormat>),
+) -> Result<Layout, WordfeudOcrError> {
+    let layout = wordfeud_ocr::Layout::new(&wordfeud_ocr::into_gray(img, format)).segment()?;
+    Ok(Layout::from(&layout))
+}
+
+#[pymethods]
+impl Board {
+    #[new]
+    fn new() -> Self {
+        Board::default()
+    }
+
+    /// Recognize a Wordfeud screenshot file.
+    pub(crate) fn recognize_screenshot_from_file(
+        &self,
+        py: Python,
+        screenshot_filename: &str,
+    ) -> PyResult<OcrResults> {
+        let board = &self.board;
+        Ok(py.allow_threads(|| recognize(board, open(screenshot_filename)?))?)
+    }
+
+    /// Recognize a Wordfeud screenshot file that is loaded in memory.
+    pub(crate) fn recognize_screenshot_from_memory(
+        &self,
+        py: Python,
+        screenshot: &[u8],
+    ) -> PyResult<OcrResults> {
+        let board = &self.board;
+        Ok(py.allow_threads(|| recognize(board, load(screenshot)?))?)
+    }
+
+    /// Recognize a Wordfeud screenshot in a NumPy `uint8` array, with shape `(H, W)` for gray,
+    /// or `(H, W, 3)` or `(H, W, 4)` for color. The `channel_order` of color arrays is `"rgb"` or `"bgr"` (OpenCV).
+    ///
+    /// The array is copied before the GIL is released.
+    #[args(channel_order = "\"rgb\"")]
+    fn recognize_array(
+        &self,
+        py: Python,
+        screenshot: PyReadonlyArrayDyn<u8>,
+        channel_order: &str,
+    ) -> PyResult<OcrResults> {
+        let screenshot = Screenshot::new(&screenshot, channel_order)?;
+        let board = &self.board;
+        let (res, layout) = py
+            .allow_threads(|| board.recognize_with_layout(&screenshot.buffer()?))
+            .map_err(WordfeudOcrError::from)?;
+        Ok(OcrResults::new(&res, &layout))
+    }
+
+    /// Find the layout of a Wordfeud screenshot file.
+    fn layout_from_file(&self, py: Python, screenshot_filename: &str) -> PyResult<Layout> {
+        Ok(py.allow_threads(|| layout(open(screenshot_filename)?))?)
+    }
+
+    /// Find the layout of a Wordfeud screenshot file that is loaded in memory.
+    fn layout_from_memory(&self, py: Python, screenshot: &[u8]) -> PyResult<Layout> {
+        Ok(py.allow_threads(|| layout(load(screenshot)?))?)
+    }
+
+    /// Find the layout of a Wordfeud screenshot in a NumPy array, see `recognize_array`.
+    #[args(channel_order = "\"rgb\"")]
+    fn layout_from_array(
+        &self,
+        py: Python,
+        screenshot: PyReadonlyArrayDyn<u8>,
+        channel_order: &str,
+    ) -> PyResult<Layout> {
+        let screenshot = Screenshot::new(&screenshot, channel_order)?;
+        let layout = py
+            .allow_threads(|| wordfeud_ocr::Layout::new(&screenshot.buffer()?).segment())
+            .map_err(WordfeudOcrError::from)?;
+        Ok(Layout::from(&layout))
+    }
+}
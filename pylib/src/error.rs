@@ -0,0 +1,111 @@
+use pyo3::{create_exception, exceptions::PyException, prelude::*, PyErr};
+use wordfeud_ocr::{Error, Segment};
+
+create_exception!(pywordfeud_ocr, WordfeudOcrException, PyException);
+create_exception!(
+    pywordfeud_ocr,
+    BoardNotSquareException,
+    WordfeudOcrException
+);
+create_exception!(pywordfeud_ocr, LayoutFailedException, WordfeudOcrException);
+create_exception!(pywordfeud_ocr, InvalidBufferException, WordfeudOcrException);
+create_exception!(pywordfeud_ocr, ImageException, WordfeudOcrException);
+
+/// Wrapper around wordfeud_ocr::Error so we convert to PyErr
+pub(crate) struct WordfeudOcrError(Error);
+
+impl From<Error> for WordfeudOcrError {
+    fn from(err: Error) -> WordfeudOcrError {
+        WordfeudOcrError(err)
+    }
+}
+
+impl From<image::ImageError> for WordfeudOcrError {
+    fn from(err: image::ImageError) -> WordfeudOcrError {
+        WordfeudOcrError(err.into())
+    }
+}
+
+/// The row or tile count of a segmentation state
+fn count(state: Segment) -> Option<usize> {
+    match state {
+        Segment::LookForTopBorder(n)
+        | Segment::LookForRisingEdge(n)
+        | Segment::InTile(n)
+        | Segment::LookForBottomBorder(n) => Some(n),
+        _ => None,
+    }
+}
+
+/// The kind of an image error, e.g. `Decoding` or `Unsupported`
+fn kind(err: &image::ImageError) -> &'static str {
+    use image::ImageError::*;
+    match err {
+        Decoding(_) => "Decoding",
+        Encoding(_) => "Encoding",
+        Parameter(_) => "Parameter",
+        Limits(_) => "Limits",
+        Unsupported(_) => "Unsupported",
+        IoError(_) => "IoError",
+    }
+}
+
+impl WordfeudOcrError {
+    /// Create the exception for the error variant, and set its attributes.
+    fn to_pyerr(&self, py: Python) -> PyResult<PyErr> {
+        let msg = self.0.message();
+        let err = match &self.0 {
+            Error::BoardNotSquare(_) => BoardNotSquareException::new_err(msg),
+            Error::LayoutFailed(_) => LayoutFailedException::new_err(msg),
+            Error::InvalidBuffer(_) => InvalidBufferException::new_err(msg),
+            Error::ImageError(_) => ImageException::new_err(msg),
+            _ => WordfeudOcrException::new_err(msg),
+        };
+        let instance = err.instance(py);
+        instance.setattr("reason", self.0.reason())?;
+        match &self.0 {
+            Error::BoardNotSquare(aspect_ratio) => {
+                instance.setattr("aspect_ratio", aspect_ratio)?
+            }
+            Error::LayoutFailed(state) => {
+                instance.setattr("segment", state.name())?;
+                instance.setattr("count", count(*state))?;
+            }
+            Error::InvalidBuffer(details) => instance.setattr("details", details)?,
+            Error::ImageError(image_error) => {
+                instance.setattr("kind", kind(image_error))?;
+                instance.setattr("image_error", image_error.to_string())?;
+            }
+            _ => {}
+        }
+        Ok(err)
+    }
+}
+
+impl From<WordfeudOcrError> for PyErr {
+    fn from(err: WordfeudOcrError) -> PyErr {
+        Python::with_gil(|py| err.to_pyerr(py).unwrap_or_else(|e| e))
+    }
+}
+
+/// Add the exception types to the module
+pub(crate) fn add_exceptions(py: Python, m: &PyModule) -> PyResult<()> {
+    m.add(
+        "WordfeudOcrException",
+        py.get_type::<WordfeudOcrException>(),
+    )?;
+    m.add(
+        "BoardNotSquareException",
+        py.get_type::<BoardNotSquareException>(),
+    )?;
+    m.add(
+        "LayoutFailedException",
+        py.get_type::<LayoutFailedException>(),
+    )?;
+    m.add(
+        "InvalidBufferException",
+        py.get_type::<InvalidBufferException>(),
+    )?;
+    m.add("ImageException", py.get_type::<ImageException>())?;
+    Ok(())
+}
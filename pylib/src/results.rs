@@ -0,0 +1,222 @@
+use crate::array::{cells_array, scores_array1, scores_array2};
+use image::math::Rect;
+use numpy::{PyArray1, PyArray2, PyArray3};
+use pyo3::prelude::*;
+use wordfeud_ocr::Ocr;
+
+/// A rectangle as a `(x, y, width, height)` tuple
+pub type PyRect = (u32, u32, u32, u32);
+
+fn rect(r: Rect) -> PyRect {
+    (r.x, r.y, r.width, r.height)
+}
+
+fn grid(ocr: &Ocr) -> Vec<Vec<String>> {
+    ocr.0.clone()
+}
+
+/// Results for a single template match
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct OcrStat {
+    /// The linear cell index (0.. nrows * ncols)
+    #[pyo3(get)]
+    pub index: usize,
+    /// The row of the cell
+    #[pyo3(get)]
+    pub row: usize,
+    /// The column of the cell
+    #[pyo3(get)]
+    pub col: usize,
+    /// The tag of the matched template
+    #[pyo3(get)]
+    pub tag: String,
+    /// The match error (the minimum value of all matched templates)
+    #[pyo3(get)]
+    pub min_value: f32,
+    /// The location where the best template match was found, relative to the matched area of the cell
+    #[pyo3(get)]
+    pub min_value_location: (u32, u32),
+    /// The cell in the screenshot `(x, y, width, height)`
+    #[pyo3(get)]
+    pub cell: PyRect,
+    /// The tags and match errors of the next best template matches, best first
+    #[pyo3(get)]
+    pub alternatives: Vec<(String, f32)>,
+}
+
+impl OcrStat {
+    /// Convert `stat`, for a grid with `cells` in rows of `cols` cells.
+    fn new(stat: &wordfeud_ocr::OcrStat, cols: usize, cells: &[Rect]) -> Self {
+        OcrStat {
+            index: stat.index,
+            row: stat.index / cols,
+            col: stat.index % cols,
+            tag: stat.tag.clone(),
+            min_value: stat.min_value,
+            min_value_location: stat.min_value_location,
+            cell: cells.get(stat.index).copied().map(rect).unwrap_or_default(),
+            alternatives: stat.alternatives.clone(),
+        }
+    }
+}
+
+fn stats(stats: &[wordfeud_ocr::OcrStat], cols: usize, cells: &[Rect]) -> Vec<OcrStat> {
+    stats
+        .iter()
+        .map(|stat| OcrStat::new(stat, cols, cells))
+        .collect()
+}
+
+/// The recognized tiles on the board and the rack, and the bonus squares
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct OcrResults {
+    /// The tiles on the board: 15 rows of 15 tiles.
+    ///
+    /// `.`: empty cell, lower case letter(s): normal tile, upper case letter(s): a blank tile used as a wildcard.
+    #[pyo3(get)]
+    pub tiles_ocr: Vec<Vec<String>>,
+    /// The bonus squares: 15 rows of 15 cells. `--`: no bonus, `2l`, `3l`, `2w`, `3w`, and `ss` for the start square.
+    #[pyo3(get)]
+    pub grid_ocr: Vec<Vec<String>>,
+    /// The 7 tiles on the rack. `.`: no tile, `*`: a blank tile.
+    #[pyo3(get)]
+    pub rack_ocr: Vec<String>,
+    /// Stats for tile recognition
+    #[pyo3(get)]
+    pub tiles_stats: Vec<OcrStat>,
+    /// Stats for grid recognition
+    #[pyo3(get)]
+    pub grid_stats: Vec<OcrStat>,
+    /// Stats for rack recognition
+    #[pyo3(get)]
+    pub rack_stats: Vec<OcrStat>,
+    /// Board area `(x, y, width, height)`
+    #[pyo3(get)]
+    pub board_area: PyRect,
+    /// Rack area `(x, y, width, height)`
+    #[pyo3(get)]
+    pub rack_area: PyRect,
+    /// The layout of the screenshot
+    #[pyo3(get)]
+    pub layout: Layout,
+}
+
+impl OcrResults {
+    /// Convert `res`, with the cell geometry from its `layout`.
+    pub fn new(res: &wordfeud_ocr::OcrResults, layout: &wordfeud_ocr::Layout) -> Self {
+        let cells = wordfeud_ocr::Layout::get_cells(&layout.rows, &layout.cols);
+        let rack_cells = wordfeud_ocr::Layout::get_cells(&layout.rack_rows, &layout.rack_cols);
+        let (cols, rack_cols) = (layout.cols.len().max(1), layout.rack_cols.len().max(1));
+        OcrResults {
+            tiles_ocr: grid(&res.tiles_ocr),
+            grid_ocr: grid(&res.grid_ocr),
+            rack_ocr: res.rack_ocr.first().cloned().unwrap_or_default(),
+            tiles_stats: stats(&res.tiles_stats, cols, &cells),
+            grid_stats: stats(&res.grid_stats, cols, &cells),
+            rack_stats: stats(&res.rack_stats, rack_cols, &rack_cells),
+            board_area: rect(res.board_area),
+            rack_area: rect(res.rack_area),
+            layout: Layout::from(layout),
+        }
+    }
+}
+
+#[pymethods]
+impl OcrResults {
+    /// The tiles on the board as 15 strings, one for each row
+    #[getter]
+    fn tiles(&self) -> Vec<String> {
+        self.tiles_ocr.iter().map(|row| row.concat()).collect()
+    }
+
+    /// The tiles on the rack as a string
+    #[getter]
+    fn rack(&self) -> String {
+        self.rack_ocr.concat()
+    }
+
+    /// The match error of each tile on the board, as a 15×15 array. NaN where there is no tile.
+    fn tiles_scores<'py>(&self, py: Python<'py>) -> PyResult<&'py PyArray2<f32>> {
+        scores_array2(py, &self.tiles_stats, (15, 15))
+    }
+
+    /// The match error of each bonus square, as a 15×15 array. NaN where there is no bonus.
+    fn grid_scores<'py>(&self, py: Python<'py>) -> PyResult<&'py PyArray2<f32>> {
+        scores_array2(py, &self.grid_stats, (15, 15))
+    }
+
+    /// The match error of each tile on the rack, as an array of 7. NaN where there is no tile.
+    fn rack_scores<'py>(&self, py: Python<'py>) -> &'py PyArray1<f32> {
+        scores_array1(py, &self.rack_stats, self.rack_ocr.len())
+    }
+}
+
+/// The layout of a Wordfeud screenshot: the board and rack area, and the rows and columns of their cells
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct Layout {
+    /// The screen area (the entire screenshot) `(x, y, width, height)`
+    #[pyo3(get)]
+    pub screen: PyRect,
+    /// The board area `(x, y, width, height)`
+    #[pyo3(get)]
+    pub board_area: PyRect,
+    /// The rack area `(x, y, width, height)`
+    #[pyo3(get)]
+    pub rack_area: PyRect,
+    /// The start and end `y` coordinate of the board rows
+    #[pyo3(get)]
+    pub rows: Vec<(usize, usize)>,
+    /// The start and end `x` coordinate of the board columns
+    #[pyo3(get)]
+    pub cols: Vec<(usize, usize)>,
+    /// The start and end `y` coordinate of the rack row
+    #[pyo3(get)]
+    pub rack_rows: Vec<(usize, usize)>,
+    /// The start and end `x` coordinate of the rack columns
+    #[pyo3(get)]
+    pub rack_cols: Vec<(usize, usize)>,
+    /// The mean and variance of each row of the screenshot, used to find the rows of the board and the rack area
+    #[pyo3(get)]
+    pub row_stats: Vec<(u32, u32)>,
+    /// The mean and variance of each column of the board area, used to find the columns of the board
+    #[pyo3(get)]
+    pub col_stats: Vec<(u32, u32)>,
+    /// The mean and variance of each column of the rack area, used to find the columns of the rack
+    #[pyo3(get)]
+    pub rack_col_stats: Vec<(u32, u32)>,
+}
+
+impl From<&wordfeud_ocr::Layout> for Layout {
+    fn from(layout: &wordfeud_ocr::Layout) -> Self {
+        Layout {
+            screen: rect(layout.screen),
+            board_area: rect(layout.board_area),
+            rack_area: rect(layout.rack_area),
+            rows: layout.rows.clone(),
+            cols: layout.cols.clone(),
+            rack_rows: layout.rack_rows.clone(),
+            rack_cols: layout.rack_cols.clone(),
+            row_stats: layout.stats(rect(layout.screen), true),
+            col_stats: layout.stats(rect(layout.board_area), false),
+            rack_col_stats: layout.stats(rect(layout.rack_area), false),
+        }
+    }
+}
+
+#[pymethods]
+impl Layout {
+    /// The cells on the board, as a 15×15×4 array with the `x, y, width, height` of each cell.
+    fn board_cells<'py>(&self, py: Python<'py>) -> PyResult<&'py PyArray3<u32>> {
+        let cells = wordfeud_ocr::Layout::get_cells(&self.rows, &self.cols);
+        cells_array(py, &cells, self.cols.len())
+    }
+
+    /// The cells on the rack, as a 1×7×4 array with the `x, y, width, height` of each cell.
+    fn rack_cells<'py>(&self, py: Python<'py>) -> PyResult<&'py PyArray3<u32>> {
+        let cells = wordfeud_ocr::Layout::get_cells(&self.rack_rows, &self.rack_cols);
+        cells_array(py, &cells, self.rack_cols.len())
+    }
+}
@@ -1,8 +1,55 @@
-from pywordfeud_ocr import recognize_screenshot_from_file
-screenshot_filename = "../lib/screenshots/screenshot_blank_tile.png"
+from pywordfeud_ocr import Board, LayoutFailedException, ImageException, recognize_screenshot_from_file
+screenshot_filename = "../lib/tests/screenshot_english.png"
 res = recognize_screenshot_from_file(screenshot_filename)
 print("State:\n{}".format('\n'.join(res['state_ocr'])))
 print("Rack: \"{}\"".format(res['rack_ocr']))
 print("Board:\n{}".format('\n'.join(res['board_ocr'])))
 print("Board area: {}".format(res['board_area']))
 print("Rack area: {}".format(res['rack_area']))
+print("Rack stats: {}".format([(stat.tag, stat.cell) for stat in res['rack_stats']]))
+
+board = Board()
+res = board.recognize_screenshot_from_file(screenshot_filename)
+print("Tiles:\n{}".format('\n'.join(res.tiles)))
+print("Rack: \"{}\"".format(res.rack))
+for stat in res.tiles_stats:
+    print("{:3} ({:2}, {:2}) {:2} {:.3f} {}".format(stat.index, stat.row, stat.col, stat.tag, stat.min_value, stat.cell))
+layout = board.layout_from_file(screenshot_filename)
+print("Board area: {}, rows: {}".format(layout.board_area, layout.rows))
+try:
+    board.recognize_screenshot_from_file("../images/logo.png")
+except LayoutFailedException as err:
+    print("Logo: {}, segment: {}".format(err.reason, err.segment))
+try:
+    board.recognize_screenshot_from_memory(b"not a screenshot")
+except ImageException as err:
+    print("Not a screenshot: {} ({})".format(err, err.kind))
+print("Row stats: {} rows, col stats: {} cols".format(len(res.layout.row_stats), len(res.layout.col_stats)))
+
+try:
+    import numpy as np
+except ImportError:
+    np = None
+if np is not None:
+    screenshot = np.zeros((1920, 1080, 3), dtype=np.uint8)
+    screenshot[:] = 255
+    try:
+        board.recognize_array(screenshot)
+    except Exception as err:
+        print("Blank screenshot: {}".format(err))
+    print("Tile scores:\n{}".format(res.tiles_scores()))
+    print("Board cells: {}".format(layout.board_cells().shape))
+
+    try:
+        from PIL import Image
+    except ImportError:
+        Image = None
+    if Image is not None:
+        # the decoded pixels of a screenshot give the same results as the screenshot file
+        from_file = board.recognize_screenshot_from_file(screenshot_filename)
+        from_array = board.recognize_array(np.asarray(Image.open(screenshot_filename).convert("RGB")))
+        assert from_array.tiles_ocr == from_file.tiles_ocr
+        assert from_array.grid_ocr == from_file.grid_ocr
+        assert from_array.rack_ocr == from_file.rack_ocr
+        assert [stat.min_value for stat in from_array.tiles_stats] == [stat.min_value for stat in from_file.tiles_stats]
+        print("Array results equal file results")
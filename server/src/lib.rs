@@ -0,0 +1,229 @@
+//! HTTP service for the wordfeud-ocr library.
+//!
+//! Endpoints:
+//! * `POST /recognize`: the body is a screenshot (PNG, JPEG or WebP). Returns the [OcrResults](wordfeud_ocr::OcrResults) as JSON.
+//! * `POST /layout`: the body is a screenshot. Returns the [Layout](wordfeud_ocr::Layout) as JSON.
+//! * `GET /health`: returns `{"status": "ok", "version": ..}`.
+//!
+//! A failure returns `{"error": {"reason": .., "message": ..}}` with a 4xx status code. The reason is
+//! [Error::reason](wordfeud_ocr::Error::reason) for a recognition error, e.g. `LayoutFailed(LookForRack)`.
+//! Screenshots that are wider or higher than [MAX_DIMENSION] pixels are rejected before they are decoded.
+use image::io::Reader as ImageReader;
+use image::{GrayImage, ImageFormat};
+use serde_json::{json, Value};
+use std::io::{self, Cursor, Read};
+use std::sync::Arc;
+use std::thread;
+use tiny_http::{Header, Method, Request, Response, Server};
+use wordfeud_ocr::{into_gray, Board, Error, Layout};
+
+/// The default limit for the size of a request body: 16 MiB
+pub const MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// The limit for the width and the height of a screenshot, in pixels
+pub const MAX_DIMENSION: u32 = 8192;
+
+/// A JSON reply with its HTTP status code
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reply {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl Reply {
+    fn ok(body: Value) -> Reply {
+        Reply { status: 200, body }
+    }
+
+    fn error(status: u16, reason: &str, message: &str) -> Reply {
+        Reply {
+            status,
+            body: json!({ "error": { "reason": reason, "message": message } }),
+        }
+    }
+}
+
+impl From<Error> for Reply {
+    fn from(err: Error) -> Self {
+        let status = match &err {
+            Error::BoardNotSquare(_) | Error::LayoutFailed(_) => 422,
+            Error::ImageError(image::ImageError::Unsupported(_)) => 415,
+            Error::ImageError(_) | Error::InvalidBuffer(_) => 400,
+            _ => 500,
+        };
+        Reply::error(status, &err.reason(), &err.message())
+    }
+}
+
+impl From<serde_json::Error> for Reply {
+    fn from(err: serde_json::Error) -> Self {
+        Reply::error(500, "SerializeFailed", &err.to_string())
+    }
+}
+
+/// The recognition service. All requests share the same [Board].
+#[derive(Debug, Clone)]
+pub struct Service {
+    board: Board,
+    max_body_size: usize,
+}
+
+impl Default for Service {
+    fn default() -> Self {
+        Service::new(Board::new(), MAX_BODY_SIZE)
+    }
+}
+
+impl Service {
+    /// Create a service that recognizes screenshots with `board`, and rejects bodies larger than `max_body_size` bytes.
+    pub fn new(board: Board, max_body_size: usize) -> Service {
+        Service {
+            board,
+            max_body_size,
+        }
+    }
+
+    /// Handle a request for `url` with `body`.
+    pub fn handle(&self, method: &Method, url: &str, body: &[u8]) -> Reply {
+        let path = url.split('?').next().unwrap_or_default();
+        let result = match (method, path) {
+            (Method::Post, "/recognize") => self.recognize(body),
+            (Method::Post, "/layout") => self.layout(body),
+            (Method::Get, "/health") => Ok(Reply::ok(
+                json!({ "status": "ok", "version": env!("CARGO_PKG_VERSION") }),
+            )),
+            (_, "/recognize") | (_, "/layout") | (_, "/health") => Err(Reply::error(
+                405,
+                "MethodNotAllowed",
+                &format!("{} is not allowed for {}", method, path),
+            )),
+            _ => Err(Reply::error(
+                404,
+                "NotFound",
+                &format!("{} not found", path),
+            )),
+        };
+        result.unwrap_or_else(|reply| reply)
+    }
+
+    fn recognize(&self, body: &[u8]) -> Result<Reply, Reply> {
+        let (gray, format) = load(body)?;
+        let res = self.board.for_format(format).recognize_screenshot(&gray)?;
+        Ok(Reply::ok(serde_json::to_value(res)?))
+    }
+
+    fn layout(&self, body: &[u8]) -> Result<Reply, Reply> {
+        let (gray, _) = load(body)?;
+        let layout = Layout::new(&gray).segment()?;
+        Ok(Reply::ok(serde_json::to_value(layout)?))
+    }
+
+    /// Read the body of `request` up to the size limit.
+    fn read_body(&self, request: &mut Request) -> Result<Vec<u8>, Reply> {
+        let too_large = || {
+            Reply::error(
+                413,
+                "PayloadTooLarge",
+                &format!("the body is larger than {} bytes", self.max_body_size),
+            )
+        };
+        if request.body_length().unwrap_or(0) > self.max_body_size {
+            return Err(too_large());
+        }
+        let mut body = Vec::new();
+        request
+            .as_reader()
+            .take(self.max_body_size as u64 + 1)
+            .read_to_end(&mut body)
+            .map_err(|err| Reply::error(400, "ReadFailed", &err.to_string()))?;
+        if body.len() > self.max_body_size {
+            return Err(too_large());
+        }
+        Ok(body)
+    }
+
+    /// Read the body of `request`, handle it and send the reply.
+    pub fn respond(&self, mut request: Request) -> io::Result<()> {
+        let reply = match self.read_body(&mut request) {
+            Ok(body) => self.handle(request.method(), request.url(), &body),
+            Err(reply) => reply,
+        };
+        log::info!("{} {} {}", request.method(), request.url(), reply.status);
+        let content_type =
+            Header::from_bytes("Content-Type", "application/json").expect("valid header");
+        let response = Response::from_string(reply.body.to_string())
+            .with_status_code(reply.status)
+            .with_header(content_type);
+        request.respond(response)
+    }
+}
+
+/// Decode the screenshot in `body` to grayscale, and return it with its format.
+/// The dimensions are checked before the pixels are decoded.
+fn load(body: &[u8]) -> Result<(GrayImage, Option<ImageFormat>), Reply> {
+    let reader = || {
+        ImageReader::new(Cursor::new(body))
+            .with_guessed_format()
+            .map_err(|err| Error::from(image::ImageError::from(err)))
+    };
+    let format = reader()?.format();
+    let (width, height) = reader()?.into_dimensions().map_err(Error::from)?;
+    if width > MAX_DIMENSION || height > MAX_DIMENSION {
+        return Err(Reply::error(
+            413,
+            "ImageTooLarge",
+            &format!(
+                "the image is {}x{} pixels, the limit is {}x{}",
+                width, height, MAX_DIMENSION, MAX_DIMENSION
+            ),
+        ));
+    }
+    let gray = into_gray(reader()?.decode().map_err(Error::from)?, format);
+    Ok((gray, format))
+}
+
+/// Handle the requests to `server` with `threads` worker threads. Returns when the server is unblocked.
+pub fn serve(server: Arc<Server>, service: Service, threads: usize) {
+    let workers: Vec<_> = (0..threads.max(1))
+        .map(|_| {
+            let server = Arc::clone(&server);
+            let service = service.clone();
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    if let Err(err) = service.respond(request) {
+                        log::warn!("Failed to send response: {}", err);
+                    }
+                }
+            })
+        })
+        .collect();
+    for worker in workers {
+        let _ = worker.join();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes() {
+        let service = Service::default();
+        let reply = service.handle(&Method::Get, "/health?verbose=1", b"");
+        assert_eq!(reply.status, 200);
+        assert_eq!(reply.body["status"], "ok");
+        assert_eq!(service.handle(&Method::Get, "/recognize", b"").status, 405);
+        assert_eq!(service.handle(&Method::Post, "/other", b"").status, 404);
+        let reply = service.handle(&Method::Post, "/layout", b"not an image");
+        assert_eq!(reply.status, 415);
+        assert_eq!(reply.body["error"]["reason"], "ImageError");
+
+        // the header of a huge image, without pixels
+        let huge = format!("P5\n{} 1\n255\n", MAX_DIMENSION + 1);
+        for url in &["/recognize", "/layout"] {
+            let reply = service.handle(&Method::Post, url, huge.as_bytes());
+            assert_eq!(reply.status, 413);
+            assert_eq!(reply.body["error"]["reason"], "ImageTooLarge");
+        }
+    }
+}
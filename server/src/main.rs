@@ -0,0 +1,44 @@
+//! HTTP server for the wordfeud-ocr library. See the library documentation for the endpoints.
+use clap::Parser;
+use std::sync::Arc;
+use wordfeud_ocr::Board;
+use wordfeud_ocr_server::{serve, Service, MAX_BODY_SIZE};
+
+#[derive(Parser)]
+#[clap(
+    name = "wordfeud-ocr-server",
+    version,
+    about = "Recognize screenshots from the Wordfeud game over HTTP"
+)]
+struct Cli {
+    /// The address to listen on
+    #[clap(short, long, default_value = "127.0.0.1:8080")]
+    addr: String,
+    /// The maximum size of a screenshot in bytes
+    #[clap(short, long, default_value_t = MAX_BODY_SIZE)]
+    max_body_size: usize,
+    /// The number of worker threads [default: the number of CPUs]
+    #[clap(short, long)]
+    threads: Option<usize>,
+}
+
+fn main() {
+    env_logger::init();
+    let cli = Cli::parse();
+    let server = match tiny_http::Server::http(&cli.addr) {
+        Ok(server) => server,
+        Err(err) => {
+            eprintln!("Failed to listen on {}: {}", cli.addr, err);
+            std::process::exit(3);
+        }
+    };
+    let threads = cli
+        .threads
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, usize::from));
+    eprintln!("Listening on http://{}", server.server_addr());
+    serve(
+        Arc::new(server),
+        Service::new(Board::new(), cli.max_body_size),
+        threads,
+    );
+}
@@ -0,0 +1,78 @@
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use tiny_http::Server;
+use wordfeud_ocr::Board;
+use wordfeud_ocr_server::{serve, Service};
+
+/// Start a server on a free localhost port, with a body size limit of `max_body_size`.
+fn start(max_body_size: usize) -> SocketAddr {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let addr = server.server_addr().to_ip().unwrap();
+    let service = Service::new(Board::new(), max_body_size);
+    thread::spawn(move || serve(Arc::new(server), service, 2));
+    addr
+}
+
+/// Send a request, and return the status code and the JSON body of the response.
+fn request(addr: SocketAddr, method: &str, path: &str, body: &[u8]) -> (u16, serde_json::Value) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        method,
+        path,
+        body.len()
+    )
+    .unwrap();
+    stream.write_all(body).unwrap();
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).unwrap();
+    let response = String::from_utf8(response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+#[test]
+fn test_server() {
+    let addr = start(4 * 1024 * 1024);
+    let screenshot = std::fs::read("../lib/tests/screenshot_english.png").unwrap();
+
+    let (status, health) = request(addr, "GET", "/health", b"");
+    assert_eq!(status, 200);
+    assert_eq!(health["status"], "ok");
+
+    let (status, result) = request(addr, "POST", "/recognize", &screenshot);
+    assert_eq!(status, 200);
+    let tiles: Vec<String> = result["tiles_ocr"][2]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tile| tile.as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(tiles.concat(), "............z..");
+
+    let (status, layout) = request(addr, "POST", "/layout", &screenshot);
+    assert_eq!(status, 200);
+    assert_eq!(layout["rows"].as_array().unwrap().len(), 15);
+    assert_eq!(layout["rack_cols"].as_array().unwrap().len(), 7);
+
+    let logo = std::fs::read("../images/logo.png").unwrap();
+    let (status, error) = request(addr, "POST", "/recognize", &logo);
+    assert_eq!(status, 422);
+    assert_eq!(error["error"]["reason"], "LayoutFailed(LookForTopBorder)");
+
+    let (status, error) = request(addr, "GET", "/recognize", b"");
+    assert_eq!(status, 405);
+    assert_eq!(error["error"]["reason"], "MethodNotAllowed");
+}
+
+#[test]
+fn test_size_limit() {
+    let addr = start(1000);
+    let (status, error) = request(addr, "POST", "/recognize", &[0; 2000]);
+    assert_eq!(status, 413);
+    assert_eq!(error["error"]["reason"], "PayloadTooLarge");
+}
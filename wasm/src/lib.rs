@@ -0,0 +1,77 @@
+//! WebAssembly bindings for the wordfeud-ocr library.
+//!
+//! The screenshot is recognized in the browser, it is not uploaded. Build the package with
+//! [wasm-bindgen](https://github.com/rustwasm/wasm-bindgen):
+//!
+//! ```shell
+//! $ cargo build --release --target wasm32-unknown-unknown -p wordfeud-ocr-wasm
+//! $ wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/wordfeud_ocr_wasm.wasm
+//! ```
+//!
+//! The result is a JavaScript object with the same fields as [OcrResults](wordfeud_ocr::OcrResults).
+//! A failure throws an `Error` with a `reason` property, see [Error::reason](wordfeud_ocr::Error::reason).
+//!
+//! ```js
+//! import init, { Recognizer } from "./pkg/wordfeud_ocr_wasm.js";
+//! await init();
+//! const recognizer = new Recognizer();
+//! const result = recognizer.recognize(new Uint8Array(await file.arrayBuffer()));
+//! console.log(result.tiles_ocr.map((row) => row.join("")).join("\n"));
+//! ```
+use wasm_bindgen::prelude::*;
+use wordfeud_ocr::{Board, Error, OcrResults, PixelFormat};
+
+/// Convert the recognition result to a JavaScript object, or a JavaScript `Error`.
+fn to_js(result: Result<OcrResults, Error>) -> Result<JsValue, JsValue> {
+    match result {
+        Ok(res) => serde_wasm_bindgen::to_value(&res).map_err(JsValue::from),
+        Err(err) => {
+            let js_err = js_sys::Error::new(&err.message());
+            js_sys::Reflect::set(&js_err, &"reason".into(), &err.reason().into())?;
+            Err(js_err.into())
+        }
+    }
+}
+
+/// Wordfeud board recognizer
+#[wasm_bindgen]
+pub struct Recognizer {
+    board: Board,
+}
+
+impl Default for Recognizer {
+    fn default() -> Self {
+        Recognizer::new()
+    }
+}
+
+#[wasm_bindgen]
+impl Recognizer {
+    /// Create a new recognizer. The templates are decoded when the first recognizer is created.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Recognizer {
+        Recognizer {
+            board: Board::new(),
+        }
+    }
+
+    /// Recognize a screenshot file (PNG, JPEG or WebP), e.g. the contents of a dropped `File`.
+    pub fn recognize(&self, screenshot: &[u8]) -> Result<JsValue, JsValue> {
+        to_js(self.board.recognize_screenshot_from_memory(screenshot))
+    }
+
+    /// Recognize the RGBA pixels of a screenshot, e.g. the `data` of a canvas `ImageData`.
+    #[wasm_bindgen(js_name = recognizeImageData)]
+    pub fn recognize_image_data(
+        &self,
+        pixels: &[u8],
+        width: u32,
+        height: u32,
+    ) -> Result<JsValue, JsValue> {
+        let stride = 4 * width as usize;
+        to_js(
+            self.board
+                .recognize_buffer(pixels, width, height, stride, PixelFormat::Rgba8),
+        )
+    }
+}
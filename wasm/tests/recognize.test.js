@@ -0,0 +1,38 @@
+// Recognize the library test screenshots with the WebAssembly build: `npm test`
+const assert = require("node:assert");
+const fs = require("node:fs");
+const path = require("node:path");
+const test = require("node:test");
+const { Recognizer } = require("../pkg/wordfeud_ocr_wasm.js");
+
+const lib = path.join(__dirname, "..", "..", "lib");
+const recognizer = new Recognizer();
+
+const grid = (ocr) => ocr.map((row) => row.join("")).join("\n");
+
+const screenshots = fs
+  .readdirSync(path.join(lib, "tests"))
+  .filter((name) => name.endsWith(".png"));
+
+for (const name of screenshots) {
+  test(`recognize ${name}`, () => {
+    const screenshot = `tests/${name}`;
+    const result = recognizer.recognize(fs.readFileSync(path.join(lib, screenshot)));
+    const ocr =
+      `Screenshot: ${screenshot}\n\nTiles:\n${grid(result.tiles_ocr)}\n\n` +
+      `Letters: ${grid(result.rack_ocr)}\n\nGrid:\n${grid(result.grid_ocr)}\n`;
+    const expect = fs.readFileSync(path.join(lib, screenshot.replace(".png", ".expect")), "utf8");
+    assert.strictEqual(ocr, expect);
+  });
+}
+
+test("errors have a reason", () => {
+  const logo = fs.readFileSync(path.join(lib, "..", "images", "logo.png"));
+  assert.throws(() => recognizer.recognize(logo), {
+    message: "Failed to create layout",
+    reason: "LayoutFailed(LookForTopBorder)",
+  });
+  assert.throws(() => recognizer.recognizeImageData(new Uint8Array(16), 1080, 1920), {
+    reason: "InvalidBuffer",
+  });
+});